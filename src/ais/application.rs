@@ -0,0 +1,359 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Decoding of binary application data carried by AIS messages
+
+use super::*;
+use std::any::Any;
+use std::sync::Arc;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decoded application specific data of binary messages. The content is identified by the
+/// designated area code (DAC) and functional ID (FID) of the message.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "data"))]
+pub enum BinaryApplication {
+    /// DAC 1, FID 22: Area notice (IMO SN.1/Circ.289)
+    AreaNotice(AreaNotice),
+
+    /// DAC 1, FID 31: Meteorological and hydrographic data (IMO SN.1/Circ.289)
+    MeteorologicalHydrographic(Box<MeteorologicalHydrographicData>),
+
+    /// DAC 1, FID 12: Dangerous cargo indication (IMO SN/Circ.236)
+    LegacyDangerousCargoIndication(LegacyDangerousCargoIndication),
+
+    /// DAC 1, FID 14 and 32: Tidal window
+    TidalWindow(TidalWindow),
+
+    /// DAC 1, FID 16 and 40: Number of persons on board
+    PersonsOnBoard(PersonsOnBoard),
+
+    /// DAC 1, FID 18: Clearance time to enter port
+    ClearanceTimeToEnterPort(ClearanceTimeToEnterPort),
+
+    /// DAC 1, FID 20: Berthing data
+    BerthingData(BerthingData),
+
+    /// DAC 1, FID 24: Extended ship static and voyage related data
+    ExtendedShipStaticData(Box<ExtendedShipStaticData>),
+
+    /// DAC 1, FID 25: Dangerous cargo indication (IMO SN.1/Circ.289)
+    DangerousCargoIndication(DangerousCargoIndication),
+
+    /// DAC 1, FID 28: Route information
+    RouteInformation(RouteInformation),
+
+    /// DAC 1, FID 30: Text description
+    TextDescription(TextDescription),
+
+    /// DAC 200, FID 10: Inland ship static and voyage related data
+    InlandStaticVoyageData(InlandStaticVoyageData),
+
+    /// DAC 200, FID 23: EMMA weather warning
+    InlandEmmaWarning(InlandEmmaWarning),
+
+    /// DAC 200, FID 24: Water levels
+    InlandWaterLevels(InlandWaterLevels),
+
+    /// DAC 200, FID 40: Signal station status
+    InlandSignalStatus(InlandSignalStatus),
+
+    /// DAC 200, FID 55: Number of persons on board
+    InlandPersonsOnBoard(InlandPersonsOnBoard),
+
+    /// Data decoded by a decoder registered with `NmeaParser::register_application_decoder`.
+    /// Only the DAC and FID are serialized and the variant can't be deserialized.
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    Custom(CustomApplication),
+}
+
+impl LatLon for BinaryApplication {
+    fn latitude(&self) -> Option<f64> {
+        match self {
+            BinaryApplication::MeteorologicalHydrographic(d) => d.latitude(),
+            BinaryApplication::TidalWindow(d) => d.latitude(),
+            BinaryApplication::ClearanceTimeToEnterPort(d) => d.latitude(),
+            BinaryApplication::BerthingData(d) => d.latitude(),
+            BinaryApplication::RouteInformation(d) => d.latitude(),
+            BinaryApplication::InlandEmmaWarning(d) => d.latitude(),
+            BinaryApplication::InlandSignalStatus(d) => d.latitude(),
+            _ => None,
+        }
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        match self {
+            BinaryApplication::MeteorologicalHydrographic(d) => d.longitude(),
+            BinaryApplication::TidalWindow(d) => d.longitude(),
+            BinaryApplication::ClearanceTimeToEnterPort(d) => d.longitude(),
+            BinaryApplication::BerthingData(d) => d.longitude(),
+            BinaryApplication::RouteInformation(d) => d.longitude(),
+            BinaryApplication::InlandEmmaWarning(d) => d.longitude(),
+            BinaryApplication::InlandSignalStatus(d) => d.longitude(),
+            _ => None,
+        }
+    }
+}
+
+/// Decode the data of a broadcast binary message with the given DAC and FID. Returns `None` if
+/// the application is not supported or the data is too short. Time fields are completed
+/// relative to `now`.
+pub(crate) fn decode_broadcast(
+    dac: u16,
+    fid: u8,
+    data: &BitVec,
+    now: DateTime<Utc>,
+) -> Option<BinaryApplication> {
+    match (dac, fid) {
+        (1, 22) => dac1_fid22::decode(data, now).map(BinaryApplication::AreaNotice),
+        (1, 31) => dac1_fid31::decode(data)
+            .map(|d| BinaryApplication::MeteorologicalHydrographic(Box::new(d))),
+        (200, 10) => {
+            dac200::decode_static_voyage_data(data).map(BinaryApplication::InlandStaticVoyageData)
+        }
+        (200, 23) => dac200::decode_emma_warning(data).map(BinaryApplication::InlandEmmaWarning),
+        (200, 24) => dac200::decode_water_levels(data).map(BinaryApplication::InlandWaterLevels),
+        (200, 40) => dac200::decode_signal_status(data).map(BinaryApplication::InlandSignalStatus),
+        (200, 55) => {
+            dac200::decode_persons_on_board(data).map(BinaryApplication::InlandPersonsOnBoard)
+        }
+        _ => None,
+    }
+}
+
+/// Decode the data of an addressed binary message with the given DAC and FID. Returns `None` if
+/// the application is not supported or the data is too short. Time fields are completed
+/// relative to `now`.
+pub(crate) fn decode_addressed(
+    dac: u16,
+    fid: u8,
+    data: &BitVec,
+    now: DateTime<Utc>,
+) -> Option<BinaryApplication> {
+    match (dac, fid) {
+        (1, _) => dac1_addressed::decode(fid, data, now),
+        (200, 55) => {
+            dac200::decode_persons_on_board(data).map(BinaryApplication::InlandPersonsOnBoard)
+        }
+        _ => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decoder for binary application data of a DAC and FID combination not supported by the crate.
+/// Decoders are registered with `NmeaParser::register_application_decoder`.
+pub trait BinaryApplicationDecoder: Send + Sync {
+    /// Type of the decoded data
+    type Output: std::fmt::Debug + PartialEq + Send + Sync + 'static;
+
+    /// Decode the application data of a binary message. Bit indices are relative to the start of
+    /// the application data. Returns `None` if the data can't be decoded.
+    fn decode(&self, data: &BitVec) -> Option<Self::Output>;
+}
+
+/// Type erased data decoded by a custom decoder
+trait CustomData: std::fmt::Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Test whether the other data is of the same type and equal to this one.
+    fn eq_data(&self, other: &dyn CustomData) -> bool;
+}
+
+impl<T: std::fmt::Debug + PartialEq + Send + Sync + 'static> CustomData for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_data(&self, other: &dyn CustomData) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// Data decoded by a custom `BinaryApplicationDecoder`. The decoded value is accessed with
+/// `downcast_ref` using the `Output` type of the decoder.
+#[derive(Clone, Debug)]
+pub struct CustomApplication {
+    dac: u16,
+    fid: u8,
+    data: Arc<dyn CustomData>,
+}
+
+impl CustomApplication {
+    /// Designated area code, DAC, of the decoded message
+    pub fn dac(&self) -> u16 {
+        self.dac
+    }
+
+    /// Functional ID, FID, of the decoded message
+    pub fn fid(&self) -> u8 {
+        self.fid
+    }
+
+    /// Return the decoded data if it's of type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        (*self.data).as_any().downcast_ref::<T>()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CustomApplication {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("CustomApplication", 2)?;
+        state.serialize_field("dac", &self.dac)?;
+        state.serialize_field("fid", &self.fid)?;
+        state.end()
+    }
+}
+
+impl PartialEq for CustomApplication {
+    fn eq(&self, other: &Self) -> bool {
+        self.dac == other.dac && self.fid == other.fid && (*self.data).eq_data(&*other.data)
+    }
+}
+
+/// Registered custom decoder with its output type erased
+pub(crate) type CustomDecoder = Arc<dyn Fn(&BitVec) -> Option<CustomApplication> + Send + Sync>;
+
+/// Wrap the given decoder of the given DAC and FID into a `CustomDecoder`.
+pub(crate) fn custom_decoder<D>(dac: u16, fid: u8, decoder: D) -> CustomDecoder
+where
+    D: BinaryApplicationDecoder + 'static,
+{
+    Arc::new(move |data| {
+        decoder.decode(data).map(|output| CustomApplication {
+            dac,
+            fid,
+            data: Arc::new(output),
+        })
+    })
+}
+
+/// Decode the data of a binary message using the decoder registered to `store` for the DAC and
+/// FID or the built-in decoder of broadcast or addressed application.
+pub(crate) fn decode(
+    store: &NmeaParser,
+    dac: u16,
+    fid: u8,
+    data: &BitVec,
+    addressed: bool,
+) -> Option<BinaryApplication> {
+    if let Some(decoder) = store.application_decoders.get(&(dac, fid)) {
+        if let Some(custom) = decoder(data) {
+            return Some(BinaryApplication::Custom(custom));
+        }
+    }
+    if addressed {
+        decode_addressed(dac, fid, data, store.feed_time)
+    } else {
+        decode_broadcast(dac, fid, data, store.feed_time)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Pick longitude in 1/1000 minutes (25 bits). Value 181 degrees means not available.
+pub(crate) fn pick_longitude_1000(bv: &BitVec, index: usize) -> Option<f64> {
+    let lon_raw = pick_i64(bv, index, 25);
+    if lon_raw != 181 * 60000 {
+        Some(lon_raw as f64 / 60000.0)
+    } else {
+        None
+    }
+}
+
+/// Pick latitude in 1/1000 minutes (24 bits). Value 91 degrees means not available.
+pub(crate) fn pick_latitude_1000(bv: &BitVec, index: usize) -> Option<f64> {
+    let lat_raw = pick_i64(bv, index, 24);
+    if lat_raw != 91 * 60000 {
+        Some(lat_raw as f64 / 60000.0)
+    } else {
+        None
+    }
+}
+
+/// Pick longitude in 1/10000 minutes (28 bits). Value 181 degrees means not available.
+pub(crate) fn pick_longitude_10000(bv: &BitVec, index: usize) -> Option<f64> {
+    let lon_raw = pick_i64(bv, index, 28);
+    if lon_raw != 181 * 600000 {
+        Some(lon_raw as f64 / 600000.0)
+    } else {
+        None
+    }
+}
+
+/// Pick latitude in 1/10000 minutes (27 bits). Value 91 degrees means not available.
+pub(crate) fn pick_latitude_10000(bv: &BitVec, index: usize) -> Option<f64> {
+    let lat_raw = pick_i64(bv, index, 27);
+    if lat_raw != 91 * 600000 {
+        Some(lat_raw as f64 / 600000.0)
+    } else {
+        None
+    }
+}
+
+/// Pick UTC month (4 bits), day (5 bits), hour (5 bits) and minute (6 bits) and complete them
+/// with the year nearest to `now`. Returns `None` if any of the fields is not available.
+pub(crate) fn pick_nearest_date_time(
+    bv: &BitVec,
+    index: usize,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    nearest_date_time(
+        pick_u64(bv, index, 4) as u32,
+        pick_u64(bv, index + 4, 5) as u32,
+        pick_u64(bv, index + 9, 5) as u32,
+        pick_u64(bv, index + 14, 6) as u32,
+        now,
+    )
+}
+
+/// Complete the given UTC month, day, hour and minute with the year nearest to `now`.
+pub(crate) fn nearest_date_time(
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    [now.year() - 1, now.year(), now.year() + 1]
+        .iter()
+        .filter_map(|year| parse_valid_utc(*year, month, day, hour, minute, 0, 0).ok())
+        .min_by_key(|t| (*t - now).num_seconds().abs())
+}
+
+/// Pick unsigned integer where zero means not available.
+pub(crate) fn pick_nonzero(bv: &BitVec, index: usize, len: usize) -> Option<u64> {
+    let raw = pick_u64(bv, index, len);
+    if raw != 0 {
+        Some(raw)
+    } else {
+        None
+    }
+}
+
+/// Pick unsigned integer which is available when it's at most `max`.
+pub(crate) fn pick_u64_max(bv: &BitVec, index: usize, len: usize, max: u64) -> Option<u64> {
+    let raw = pick_u64(bv, index, len);
+    if raw <= max {
+        Some(raw)
+    } else {
+        None
+    }
+}
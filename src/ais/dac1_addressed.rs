@@ -0,0 +1,975 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! DAC 1: International addressed application messages (IMO SN/Circ.236 and SN.1/Circ.289)
+
+use super::*;
+use application::{
+    nearest_date_time, pick_latitude_1000, pick_latitude_10000, pick_longitude_1000,
+    pick_longitude_10000, pick_nearest_date_time, pick_nonzero, pick_u64_max,
+};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Unit of cargo quantity
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuantityUnit {
+    Kilograms,
+    Tonnes,
+    KiloTonnes,
+}
+
+impl QuantityUnit {
+    fn new(raw: u8) -> Option<QuantityUnit> {
+        match raw {
+            1 => Some(QuantityUnit::Kilograms),
+            2 => Some(QuantityUnit::Tonnes),
+            3 => Some(QuantityUnit::KiloTonnes),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for QuantityUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuantityUnit::Kilograms => write!(f, "kg"),
+            QuantityUnit::Tonnes => write!(f, "t"),
+            QuantityUnit::KiloTonnes => write!(f, "kt"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 12: Dangerous cargo indication (IMO SN/Circ.236)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyDangerousCargoIndication {
+    /// Last port of call (UN/LOCODE)
+    pub last_port: Option<String>,
+
+    /// Actual time of departure from the last port of call
+    pub departure_time: Option<DateTime<Utc>>,
+
+    /// Next port of call (UN/LOCODE)
+    pub next_port: Option<String>,
+
+    /// Estimated time of arrival to the next port of call
+    pub eta: Option<DateTime<Utc>>,
+
+    /// Main dangerous good
+    pub main_dangerous_good: Option<String>,
+
+    /// IMD category of the main dangerous good
+    pub imd_category: Option<String>,
+
+    /// UN number of the main dangerous good
+    pub un_number: Option<u16>,
+
+    /// Quantity of the main dangerous good
+    pub quantity: Option<u16>,
+
+    /// Unit of `quantity`
+    pub quantity_unit: Option<QuantityUnit>,
+}
+
+fn decode_legacy_dangerous_cargo(
+    bv: &BitVec,
+    now: DateTime<Utc>,
+) -> Option<LegacyDangerousCargoIndication> {
+    if bv.len() < 269 {
+        return None;
+    }
+    Some(LegacyDangerousCargoIndication {
+        last_port: pick_text(bv, 0, 5),
+        departure_time: pick_nearest_date_time(bv, 30, now),
+        next_port: pick_text(bv, 50, 5),
+        eta: pick_nearest_date_time(bv, 80, now),
+        main_dangerous_good: pick_text(bv, 100, 20),
+        imd_category: pick_text(bv, 220, 4),
+        un_number: pick_nonzero(bv, 244, 13).map(|v| v as u16),
+        quantity: pick_nonzero(bv, 257, 10).map(|v| v as u16),
+        quantity_unit: QuantityUnit::new(pick_u64(bv, 267, 2) as u8),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 25: Dangerous cargo indication (IMO SN.1/Circ.289)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DangerousCargoIndication {
+    /// Unit of `quantity`
+    pub quantity_unit: Option<QuantityUnit>,
+
+    /// Total quantity of dangerous cargo
+    pub quantity: Option<u16>,
+
+    /// Dangerous cargo carried
+    pub cargos: Vec<DangerousCargo>,
+}
+
+/// Dangerous cargo identified by the code under which it is carried
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "data"))]
+pub enum DangerousCargo {
+    /// IMDG Code, class or division
+    Imdg { class: u8 },
+
+    /// IGC Code, UN number
+    Igc { un_number: u16 },
+
+    /// BC Code, dangerous cargo group and IMDG class or division
+    Bc { group: u8, imdg_class: u8 },
+
+    /// MARPOL Annex I, type of oil
+    MarpolAnnexI { oil_type: u8 },
+
+    /// MARPOL Annex II (IBC Code), pollution category
+    MarpolAnnexII { category: u8 },
+
+    /// Regional use
+    Regional { code: u16 },
+}
+
+fn decode_dangerous_cargo(bv: &BitVec) -> Option<DangerousCargoIndication> {
+    if bv.len() < 12 {
+        return None;
+    }
+    let count = ((bv.len() - 12) / 17).min(28);
+    Some(DangerousCargoIndication {
+        quantity_unit: QuantityUnit::new(pick_u64(bv, 0, 2) as u8),
+        quantity: pick_nonzero(bv, 2, 10).map(|v| v as u16),
+        cargos: (0..count)
+            .filter_map(|i| {
+                let index = 12 + i * 17;
+                match pick_u64(bv, index, 4) {
+                    1 => Some(DangerousCargo::Imdg {
+                        class: pick_u64(bv, index + 4, 7) as u8,
+                    }),
+                    2 => Some(DangerousCargo::Igc {
+                        un_number: pick_u64(bv, index + 4, 13) as u16,
+                    }),
+                    3 => Some(DangerousCargo::Bc {
+                        group: pick_u64(bv, index + 4, 3) as u8,
+                        imdg_class: pick_u64(bv, index + 7, 7) as u8,
+                    }),
+                    4 => Some(DangerousCargo::MarpolAnnexI {
+                        oil_type: pick_u64(bv, index + 4, 4) as u8,
+                    }),
+                    5 => Some(DangerousCargo::MarpolAnnexII {
+                        category: pick_u64(bv, index + 4, 3) as u8,
+                    }),
+                    6 => Some(DangerousCargo::Regional {
+                        code: pick_u64(bv, index + 4, 13) as u16,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect(),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 14 (IMO SN/Circ.236) and FID 32 (IMO SN.1/Circ.289): Tidal window
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TidalWindow {
+    /// Tidal window periods at the given positions
+    pub periods: Vec<TidalWindowPeriod>,
+}
+
+impl LatLon for TidalWindow {
+    fn latitude(&self) -> Option<f64> {
+        self.periods.first().and_then(|p| p.latitude)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.periods.first().and_then(|p| p.longitude)
+    }
+}
+
+/// Tidal window at a position
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TidalWindowPeriod {
+    /// Latitude of the position
+    pub latitude: Option<f64>,
+
+    /// Longitude of the position
+    pub longitude: Option<f64>,
+
+    /// Start of the tidal window
+    pub from: Option<DateTime<Utc>>,
+
+    /// End of the tidal window
+    pub to: Option<DateTime<Utc>>,
+
+    /// Direction of the current in degrees
+    pub current_direction: Option<u16>,
+
+    /// Speed of the current in knots
+    pub current_speed_knots: Option<f64>,
+}
+
+impl LatLon for TidalWindowPeriod {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+fn decode_tidal_window(bv: &BitVec, legacy: bool, now: DateTime<Utc>) -> Option<TidalWindow> {
+    let period_length = if legacy { 93 } else { 88 };
+    if bv.len() < 9 + period_length {
+        return None;
+    }
+    let month = pick_u64(bv, 0, 4) as u32;
+    let day = pick_u64(bv, 4, 5) as u32;
+    let count = ((bv.len() - 9) / period_length).min(3);
+    Some(TidalWindow {
+        periods: (0..count)
+            .map(|i| {
+                let index = 9 + i * period_length;
+                let (latitude, longitude, index) = if legacy {
+                    (
+                        pick_latitude_10000(bv, index),
+                        pick_longitude_10000(bv, index + 27),
+                        index + 55,
+                    )
+                } else {
+                    (
+                        pick_latitude_1000(bv, index + 25),
+                        pick_longitude_1000(bv, index),
+                        index + 49,
+                    )
+                };
+                let pick_time = |index| {
+                    nearest_date_time(
+                        month,
+                        day,
+                        pick_u64(bv, index, 5) as u32,
+                        pick_u64(bv, index + 5, 6) as u32,
+                        now,
+                    )
+                };
+                TidalWindowPeriod {
+                    latitude,
+                    longitude,
+                    from: pick_time(index),
+                    to: pick_time(index + 11),
+                    current_direction: pick_u64_max(bv, index + 22, 9, 359).map(|v| v as u16),
+                    current_speed_knots: if legacy {
+                        pick_u64_max(bv, index + 31, 7, 126)
+                    } else {
+                        pick_u64_max(bv, index + 31, 8, 250)
+                    }
+                    .map(|v| v as f64 / 10.0),
+                }
+            })
+            .collect(),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 16 (IMO SN/Circ.236) and FID 40 (IMO SN.1/Circ.289): Number of persons on board
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersonsOnBoard {
+    /// Number of persons currently on board including crew members
+    pub persons: Option<u16>,
+}
+
+fn decode_persons_on_board(bv: &BitVec) -> Option<PersonsOnBoard> {
+    if bv.len() < 13 {
+        return None;
+    }
+    Some(PersonsOnBoard {
+        persons: pick_u64_max(bv, 0, 13, 8190).map(|v| v as u16),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 28: Route information (IMO SN.1/Circ.289)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteInformation {
+    /// Message linkage ID (10 bits)
+    pub message_linkage_id: u16,
+
+    /// True if sent by a competent authority, false if sent by a ship
+    pub sent_by_authority: bool,
+
+    /// Route type
+    pub route_type: Option<RouteType>,
+
+    /// Start time of the route
+    pub start_time: Option<DateTime<Utc>>,
+
+    /// Duration of the route in minutes, `None` if undefined
+    pub duration_minutes: Option<u32>,
+
+    /// Waypoints of the route
+    pub waypoints: Vec<AreaPoint>,
+}
+
+impl LatLon for RouteInformation {
+    fn latitude(&self) -> Option<f64> {
+        self.waypoints.first().map(|p| p.latitude)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.waypoints.first().map(|p| p.longitude)
+    }
+}
+
+/// Type of route
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RouteType {
+    Mandatory,
+    Recommended,
+    Alternative,
+    RecommendedThroughIce,
+    ShipRoutePlan,
+    Cancellation,
+}
+
+impl RouteType {
+    fn new(raw: u8) -> Option<RouteType> {
+        match raw {
+            1 => Some(RouteType::Mandatory),
+            2 => Some(RouteType::Recommended),
+            3 => Some(RouteType::Alternative),
+            4 => Some(RouteType::RecommendedThroughIce),
+            5 => Some(RouteType::ShipRoutePlan),
+            31 => Some(RouteType::Cancellation),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RouteType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteType::Mandatory => write!(f, "mandatory route"),
+            RouteType::Recommended => write!(f, "recommended route"),
+            RouteType::Alternative => write!(f, "alternative route"),
+            RouteType::RecommendedThroughIce => write!(f, "recommended route through ice"),
+            RouteType::ShipRoutePlan => write!(f, "ship route plan"),
+            RouteType::Cancellation => write!(f, "cancellation"),
+        }
+    }
+}
+
+fn decode_route_information(bv: &BitVec, now: DateTime<Utc>) -> Option<RouteInformation> {
+    if bv.len() < 61 {
+        return None;
+    }
+    let count = (pick_u64(bv, 56, 5) as usize).min((bv.len() - 61) / 55);
+    Some(RouteInformation {
+        message_linkage_id: pick_u64(bv, 0, 10) as u16,
+        sent_by_authority: pick_u64(bv, 10, 3) == 1,
+        route_type: RouteType::new(pick_u64(bv, 13, 5) as u8),
+        start_time: pick_nearest_date_time(bv, 18, now),
+        duration_minutes: pick_u64_max(bv, 38, 18, 262142).map(|v| v as u32),
+        waypoints: (0..count)
+            .filter_map(|i| {
+                let index = 61 + i * 55;
+                let longitude = pick_longitude_10000(bv, index)?;
+                let latitude = pick_latitude_10000(bv, index + 28)?;
+                Some(AreaPoint {
+                    latitude,
+                    longitude,
+                })
+            })
+            .collect(),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 30: Text description (IMO SN.1/Circ.289)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextDescription {
+    /// Message linkage ID (10 bits)
+    pub message_linkage_id: u16,
+
+    /// Text of up to 161 characters
+    pub text: String,
+}
+
+fn decode_text_description(bv: &BitVec) -> Option<TextDescription> {
+    if bv.len() < 10 {
+        return None;
+    }
+    let char_count = ((bv.len() - 10) / 6).min(161);
+    Some(TextDescription {
+        message_linkage_id: pick_u64(bv, 0, 10) as u16,
+        text: pick_string(bv, 10, char_count).trim_end().into(),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 24: Extended ship static and voyage related data (IMO SN.1/Circ.289)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedShipStaticData {
+    /// Message linkage ID (10 bits)
+    pub message_linkage_id: u16,
+
+    /// Air draught in metres (0.1 m resolution)
+    pub air_draught: Option<f64>,
+
+    /// Last port of call (UN/LOCODE)
+    pub last_port: Option<String>,
+
+    /// Next ports of call (UN/LOCODE)
+    pub next_ports: Vec<String>,
+
+    /// Status of the 26 SOLAS equipment items in the order of SN.1/Circ.289:
+    /// true = operational, false = not operational
+    pub solas_status: Vec<Option<bool>>,
+
+    /// Ice class (0-14)
+    pub ice_class: Option<u8>,
+
+    /// Shaft horse power
+    pub shaft_horse_power: Option<u32>,
+
+    /// VHF working channel
+    pub vhf_channel: Option<u16>,
+
+    /// Lloyd's ship type
+    pub lloyds_ship_type: Option<String>,
+
+    /// Gross tonnage
+    pub gross_tonnage: Option<u32>,
+
+    /// True if laden, false if in ballast
+    pub laden: Option<bool>,
+
+    /// Heavy fuel oil bunker on board
+    pub heavy_fuel_oil: Option<bool>,
+
+    /// Light fuel oil bunker on board
+    pub light_fuel_oil: Option<bool>,
+
+    /// Diesel bunker on board
+    pub diesel: Option<bool>,
+
+    /// Total amount of bunker oil in tonnes
+    pub bunker_oil_tonnes: Option<u16>,
+
+    /// Number of persons on board
+    pub persons: Option<u16>,
+}
+
+fn decode_extended_ship_static_data(bv: &BitVec) -> Option<ExtendedShipStaticData> {
+    if bv.len() < 294 {
+        return None;
+    }
+    let pick_flag = |index| match pick_u64(bv, index, 2) {
+        1 => Some(true),
+        2 => Some(false),
+        _ => None,
+    };
+    Some(ExtendedShipStaticData {
+        message_linkage_id: pick_u64(bv, 0, 10) as u16,
+        air_draught: pick_nonzero(bv, 10, 13).map(|v| v as f64 / 10.0),
+        last_port: pick_text(bv, 23, 5),
+        next_ports: [53, 83]
+            .iter()
+            .filter_map(|index| pick_text(bv, *index, 5))
+            .collect(),
+        solas_status: (0..26).map(|i| pick_flag(113 + i * 2)).collect(),
+        ice_class: pick_u64_max(bv, 165, 4, 14).map(|v| v as u8),
+        shaft_horse_power: pick_u64_max(bv, 169, 18, 262142).map(|v| v as u32),
+        vhf_channel: pick_nonzero(bv, 187, 12).map(|v| v as u16),
+        lloyds_ship_type: pick_text(bv, 199, 7),
+        gross_tonnage: pick_u64_max(bv, 241, 18, 262142).map(|v| v as u32),
+        laden: pick_flag(259),
+        heavy_fuel_oil: pick_flag(261).map(|v| !v),
+        light_fuel_oil: pick_flag(263).map(|v| !v),
+        diesel: pick_flag(265).map(|v| !v),
+        bunker_oil_tonnes: pick_u64_max(bv, 267, 14, 16382).map(|v| v as u16),
+        persons: pick_u64_max(bv, 281, 13, 8190).map(|v| v as u16),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 18: Clearance time to enter port (IMO SN.1/Circ.289)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClearanceTimeToEnterPort {
+    /// Message linkage ID (10 bits)
+    pub message_linkage_id: u16,
+
+    /// Clearance time to enter port
+    pub clearance_time: Option<DateTime<Utc>>,
+
+    /// Name of the port and berth
+    pub port_and_berth: Option<String>,
+
+    /// Destination (UN/LOCODE)
+    pub destination: Option<String>,
+
+    /// Latitude of the position
+    pub latitude: Option<f64>,
+
+    /// Longitude of the position
+    pub longitude: Option<f64>,
+}
+
+impl LatLon for ClearanceTimeToEnterPort {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+fn decode_clearance_time(bv: &BitVec, now: DateTime<Utc>) -> Option<ClearanceTimeToEnterPort> {
+    if bv.len() < 229 {
+        return None;
+    }
+    Some(ClearanceTimeToEnterPort {
+        message_linkage_id: pick_u64(bv, 0, 10) as u16,
+        clearance_time: pick_nearest_date_time(bv, 10, now),
+        port_and_berth: pick_text(bv, 30, 20),
+        destination: pick_text(bv, 150, 5),
+        longitude: pick_longitude_1000(bv, 180),
+        latitude: pick_latitude_1000(bv, 205),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// FID 20: Berthing data (IMO SN.1/Circ.289)
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BerthingData {
+    /// Message linkage ID (10 bits)
+    pub message_linkage_id: u16,
+
+    /// Berth length in metres
+    pub berth_length: Option<u16>,
+
+    /// Water depth at berth in metres (0.1 m resolution)
+    pub berth_depth: Option<f64>,
+
+    /// Mooring position
+    pub mooring_position: Option<MooringPosition>,
+
+    /// Berthing time
+    pub berthing_time: Option<DateTime<Utc>>,
+
+    /// Availability of the 26 services in the order of SN.1/Circ.289 (agent, fuel, chandler,
+    /// stevedore, electrical, water, customs, cartage, crane, lift, medical, navigation repair,
+    /// provisions, ship repair, surveyor, steam, tugs, solid waste, liquid waste, hazardous
+    /// waste, ballast, additional, regional 1-2, reserved 1-2). Empty if not available.
+    pub services: Vec<Option<bool>>,
+
+    /// Name of the berth
+    pub berth_name: Option<String>,
+
+    /// Latitude of the berth
+    pub latitude: Option<f64>,
+
+    /// Longitude of the berth
+    pub longitude: Option<f64>,
+}
+
+impl LatLon for BerthingData {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+/// Mooring position at berth
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MooringPosition {
+    PortSideTo,
+    StarboardSideTo,
+    MediterraneanMooring,
+    MooringBuoy,
+    Anchorage,
+}
+
+impl MooringPosition {
+    fn new(raw: u8) -> Option<MooringPosition> {
+        match raw {
+            1 => Some(MooringPosition::PortSideTo),
+            2 => Some(MooringPosition::StarboardSideTo),
+            3 => Some(MooringPosition::MediterraneanMooring),
+            4 => Some(MooringPosition::MooringBuoy),
+            5 => Some(MooringPosition::Anchorage),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MooringPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MooringPosition::PortSideTo => write!(f, "port-side to"),
+            MooringPosition::StarboardSideTo => write!(f, "starboard-side to"),
+            MooringPosition::MediterraneanMooring => write!(f, "Mediterranean mooring"),
+            MooringPosition::MooringBuoy => write!(f, "mooring buoy"),
+            MooringPosition::Anchorage => write!(f, "anchorage"),
+        }
+    }
+}
+
+fn decode_berthing_data(bv: &BitVec, now: DateTime<Utc>) -> Option<BerthingData> {
+    if bv.len() < 272 {
+        return None;
+    }
+    Some(BerthingData {
+        message_linkage_id: pick_u64(bv, 0, 10) as u16,
+        berth_length: pick_nonzero(bv, 10, 9).map(|v| v as u16),
+        berth_depth: pick_nonzero(bv, 19, 8).map(|v| v as f64 / 10.0),
+        mooring_position: MooringPosition::new(pick_u64(bv, 27, 3) as u8),
+        berthing_time: pick_nearest_date_time(bv, 30, now),
+        services: if pick_u64(bv, 50, 1) != 0 {
+            (0..26)
+                .map(|i| match pick_u64(bv, 51 + i * 2, 2) {
+                    0 => Some(false),
+                    1 => Some(true),
+                    _ => None,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        },
+        berth_name: pick_text(bv, 103, 20),
+        longitude: pick_longitude_1000(bv, 223),
+        latitude: pick_latitude_1000(bv, 248),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decode the data of an addressed binary message with DAC 1 and the given FID.
+pub(crate) fn decode(fid: u8, bv: &BitVec, now: DateTime<Utc>) -> Option<BinaryApplication> {
+    match fid {
+        12 => decode_legacy_dangerous_cargo(bv, now)
+            .map(BinaryApplication::LegacyDangerousCargoIndication),
+        14 => decode_tidal_window(bv, true, now).map(BinaryApplication::TidalWindow),
+        16 | 40 => decode_persons_on_board(bv).map(BinaryApplication::PersonsOnBoard),
+        18 => decode_clearance_time(bv, now).map(BinaryApplication::ClearanceTimeToEnterPort),
+        20 => decode_berthing_data(bv, now).map(BinaryApplication::BerthingData),
+        24 => decode_extended_ship_static_data(bv)
+            .map(|d| BinaryApplication::ExtendedShipStaticData(Box::new(d))),
+        25 => decode_dangerous_cargo(bv).map(BinaryApplication::DangerousCargoIndication),
+        28 => decode_route_information(bv, now).map(BinaryApplication::RouteInformation),
+        30 => decode_text_description(bv).map(BinaryApplication::TextDescription),
+        32 => decode_tidal_window(bv, false, now).map(BinaryApplication::TidalWindow),
+        _ => None,
+    }
+}
+
+/// Pick trimmed text where an empty string means not available.
+fn pick_text(bv: &BitVec, index: usize, char_count: usize) -> Option<String> {
+    let text = pick_string(bv, index, char_count);
+    let text = text.trim();
+    if !text.is_empty() {
+        Some(text.into())
+    } else {
+        None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Parse the given addressed binary message sentences and return the message.
+    fn parse_addressed(sentences: &[&str]) -> BinaryAddressedMessage {
+        let mut p = NmeaParser::new();
+        p.set_clock(Clock::Fixed(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
+        for s in sentences {
+            match p.parse_sentence(s) {
+                Ok(ParsedMessage::BinaryAddressedMessage(bam)) => {
+                    return bam;
+                }
+                Ok(ParsedMessage::Incomplete) => {}
+                Ok(_) => {
+                    assert!(false);
+                }
+                Err(e) => {
+                    assert_eq!(e.to_string(), "OK");
+                }
+            }
+        }
+        BinaryAddressedMessage::default()
+    }
+
+    #[test]
+    fn test_parse_dac1_fid12() {
+        let bam = parse_addressed(&[
+            "!AIVDM,1,1,,A,63`l7@0o4UpH04hHTPDiA:NC51B>DIP0CCCkRECH3RE4PE1@000=Kd@3jiu@,0*17",
+        ]);
+        assert_eq!(bam.destination_mmsi, 230987654);
+        match bam.application {
+            Some(BinaryApplication::LegacyDangerousCargoIndication(dci)) => {
+                assert_eq!(dci.last_port, Some("FIHEL".into()));
+                assert_eq!(
+                    dci.departure_time,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(10, 30, 0))
+                );
+                assert_eq!(dci.next_port, Some("SEARN".into()));
+                assert_eq!(dci.eta, Some(Utc.ymd(2021, 5, 3).and_hms(6, 0, 0)));
+                assert_eq!(dci.main_dangerous_good, Some("AMMONIUM NITRATE".into()));
+                assert_eq!(dci.imd_category, Some("5.1".into()));
+                assert_eq!(dci.un_number, Some(1942));
+                assert_eq!(dci.quantity, Some(250));
+                assert_eq!(dci.quantity_unit, Some(QuantityUnit::Tonnes));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid14_fid32() {
+        let bam = parse_addressed(&["!AIVDM,1,1,,A,63`l7@0o4UpH04qA7akb04``H50>NQht,2*43"]);
+        assert::close(bam.latitude().unwrap_or(0.0), 53.5, 0.000001);
+        assert::close(bam.longitude().unwrap_or(0.0), 8.1, 0.000001);
+        match bam.application {
+            Some(BinaryApplication::TidalWindow(tw)) => {
+                assert_eq!(tw.periods.len(), 1);
+                assert_eq!(
+                    tw.periods[0].from,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(10, 0, 0))
+                );
+                assert_eq!(
+                    tw.periods[0].to,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(14, 30, 0))
+                );
+                assert_eq!(tw.periods[0].current_direction, Some(270));
+                assert_eq!(tw.periods[0].current_speed_knots, Some(1.5));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        let bam =
+            parse_addressed(&["!AIVDM,1,1,,A,63`l7@0o4UpH061A1K;t3L5t40<0;@H5f=P=fr0`1L0e1P,3*15"]);
+        match bam.application {
+            Some(BinaryApplication::TidalWindow(tw)) => {
+                assert_eq!(tw.periods.len(), 2);
+                assert::close(tw.periods[1].latitude.unwrap_or(0.0), 60.0, 0.000001);
+                assert::close(tw.periods[1].longitude.unwrap_or(0.0), 25.0, 0.000001);
+                assert_eq!(
+                    tw.periods[1].from,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(20, 0, 0))
+                );
+                assert_eq!(
+                    tw.periods[1].to,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(23, 0, 0))
+                );
+                assert_eq!(tw.periods[1].current_direction, Some(90));
+                assert_eq!(tw.periods[1].current_speed_knots, Some(1.2));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid16_fid40() {
+        let bam = parse_addressed(&["!AIVDM,1,1,,A,63`l7@0o4UpH0500j0,4*05"]);
+        assert_eq!(
+            bam.application,
+            Some(BinaryApplication::PersonsOnBoard(PersonsOnBoard {
+                persons: Some(25)
+            }))
+        );
+        let bam = parse_addressed(&["!AIVDM,1,1,,A,63`l7@0o4UpH06Swv0,4*3E"]);
+        assert_eq!(
+            bam.application,
+            Some(BinaryApplication::PersonsOnBoard(PersonsOnBoard {
+                persons: None
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_dac1_fid18() {
+        let bam = parse_addressed(&[
+            "!AIVDM,1,1,,A,63`l7@0o4UpH0581m52m0u9B0tJ0PDi<TpdT0000HTPDh;Ju@KRP00000000,0*6D",
+        ]);
+        assert::close(bam.latitude().unwrap_or(0.0), 60.16, 0.000001);
+        assert::close(bam.longitude().unwrap_or(0.0), 24.95, 0.000001);
+        match bam.application {
+            Some(BinaryApplication::ClearanceTimeToEnterPort(ct)) => {
+                assert_eq!(ct.message_linkage_id, 7);
+                assert_eq!(
+                    ct.clearance_time,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(16, 45, 0))
+                );
+                assert_eq!(ct.port_and_berth, Some("PORT OF HELSINKI".into()));
+                assert_eq!(ct.destination, Some("FIHEL".into()));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid20() {
+        let bam = parse_addressed(&[
+            "!AIVDM,1,1,,A,63`l7@0o4UpH05@26TgUAB0a:bbbbbbb4:T`A1ST0000000000005e`0=i6`,0*3C",
+        ]);
+        assert::close(bam.latitude().unwrap_or(0.0), 60.15, 0.000001);
+        assert::close(bam.longitude().unwrap_or(0.0), 24.96, 0.000001);
+        match bam.application {
+            Some(BinaryApplication::BerthingData(bd)) => {
+                assert_eq!(bd.message_linkage_id, 8);
+                assert_eq!(bd.berth_length, Some(210));
+                assert_eq!(bd.berth_depth, Some(9.5));
+                assert_eq!(bd.mooring_position, Some(MooringPosition::PortSideTo));
+                assert_eq!(
+                    bd.berthing_time,
+                    Some(Utc.ymd(2021, 5, 2).and_hms(18, 0, 0))
+                );
+                assert_eq!(bd.services.len(), 26);
+                assert_eq!(bd.services[0], Some(true));
+                assert_eq!(bd.services[1], Some(false));
+                assert_eq!(bd.services[2], None);
+                assert_eq!(bd.berth_name, Some("BERTH 12".into()));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid24() {
+        let bam = parse_addressed(&[
+            "!AIVDM,2,1,5,A,63`l7@0o4UpH05P2@h8i90aRH`:Ah00002bbbbbbbbV5o00P3WR3T>h<=@e2,0*08",
+            "!AIVDM,2,2,5,A,F01D00,4*14",
+        ]);
+        match bam.application {
+            Some(BinaryApplication::ExtendedShipStaticData(ess)) => {
+                assert_eq!(ess.message_linkage_id, 9);
+                assert_eq!(ess.air_draught, Some(38.5));
+                assert_eq!(ess.last_port, Some("FIHEL".into()));
+                assert_eq!(ess.next_ports, vec!["SEARN".to_string()]);
+                assert_eq!(ess.solas_status, vec![Some(true); 26]);
+                assert_eq!(ess.ice_class, Some(3));
+                assert_eq!(ess.shaft_horse_power, Some(12000));
+                assert_eq!(ess.vhf_channel, Some(16));
+                assert_eq!(ess.lloyds_ship_type, Some("A31A2GX".into()));
+                assert_eq!(ess.gross_tonnage, Some(25000));
+                assert_eq!(ess.laden, Some(true));
+                assert_eq!(ess.heavy_fuel_oil, Some(false));
+                assert_eq!(ess.light_fuel_oil, Some(true));
+                assert_eq!(ess.diesel, Some(true));
+                assert_eq!(ess.bunker_oil_tonnes, Some(1200));
+                assert_eq!(ess.persons, Some(21));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid25() {
+        let bam = parse_addressed(&["!AIVDM,1,1,,A,63`l7@0o4UpH05VO@FH13sE@00,5*7D"]);
+        match bam.application {
+            Some(BinaryApplication::DangerousCargoIndication(dci)) => {
+                assert_eq!(dci.quantity_unit, Some(QuantityUnit::Tonnes));
+                assert_eq!(dci.quantity, Some(500));
+                assert_eq!(
+                    dci.cargos,
+                    vec![
+                        DangerousCargo::Imdg { class: 51 },
+                        DangerousCargo::Igc { un_number: 1005 },
+                        DangerousCargo::MarpolAnnexII { category: 2 },
+                    ]
+                );
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid28() {
+        let bam = parse_addressed(&[
+            "!AIVDM,1,1,,A,63`l7@0o4UpH05h2j9A<009H677sd29SeP3TpL14bR01kFl0RAV`0,4*1D",
+        ]);
+        assert::close(bam.latitude().unwrap_or(0.0), 60.1, 0.000001);
+        assert::close(bam.longitude().unwrap_or(0.0), 24.9, 0.000001);
+        match bam.application {
+            Some(BinaryApplication::RouteInformation(ri)) => {
+                assert_eq!(ri.message_linkage_id, 11);
+                assert!(ri.sent_by_authority);
+                assert_eq!(ri.route_type, Some(RouteType::Recommended));
+                assert_eq!(ri.start_time, Some(Utc.ymd(2021, 5, 2).and_hms(12, 0, 0)));
+                assert_eq!(ri.duration_minutes, Some(600));
+                assert_eq!(ri.waypoints.len(), 3);
+                assert::close(ri.waypoints[2].latitude, 59.9, 0.000001);
+                assert::close(ri.waypoints[2].longitude, 25.2, 0.000001);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac1_fid30() {
+        let bam = parse_addressed(&["!AIVDM,1,1,,A,63`l7@0o4UpH05p342C3m83k`0ShDQ80E8<Ld<0,4*74"]);
+        assert_eq!(
+            bam.application,
+            Some(BinaryApplication::TextDescription(TextDescription {
+                message_linkage_id: 12,
+                text: "PILOT ON BOARD AT 1200".into(),
+            }))
+        );
+    }
+}
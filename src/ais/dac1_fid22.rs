@@ -0,0 +1,1131 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! DAC 1, FID 22: Area notice (IMO SN.1/Circ.289)
+
+use super::*;
+use application::{pick_latitude_1000, pick_longitude_1000, pick_nearest_date_time};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Length of the area notice header in bits
+const HEADER_LENGTH: usize = 55;
+
+/// Length of one sub-area in bits
+const SUB_AREA_LENGTH: usize = 87;
+
+/// Maximum number of sub-areas in one area notice
+const MAX_SUB_AREAS: usize = 10;
+
+/// Area notice broadcast in AIS type 8 message with DAC 1 and FID 22.
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AreaNotice {
+    /// Message linkage ID used for linking the notice to other messages (10 bits)
+    pub message_linkage_id: u16,
+
+    /// Area notice type
+    pub notice_type: AreaNoticeType,
+
+    /// Start time of the notice. The year is chosen to be the nearest to the receive time.
+    pub start_time: Option<DateTime<Utc>>,
+
+    /// Duration of the notice in minutes, `None` if undefined
+    pub duration_minutes: Option<u32>,
+
+    /// Sub-areas defining the notice area. Continued polylines, polygons and texts are combined.
+    pub sub_areas: Vec<AreaNoticeSubArea>,
+}
+
+/// Sub-area of an area notice. Positions are rounded to the precision given in the message and
+/// distances are scaled to metres.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "data"))]
+pub enum AreaNoticeSubArea {
+    /// Circle or point when radius is zero
+    Circle {
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        radius_m: u32,
+    },
+
+    /// Rectangle with its south-west corner at the given position
+    Rectangle {
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        east_dimension_m: u32,
+        north_dimension_m: u32,
+        orientation: u16,
+    },
+
+    /// Sector between left and right boundaries given in degrees
+    Sector {
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        radius_m: u32,
+        left_boundary: u16,
+        right_boundary: u16,
+    },
+
+    /// Polyline starting from the position of the previous sub-area
+    Polyline { points: Vec<AreaPoint> },
+
+    /// Polygon starting from the position of the previous sub-area
+    Polygon { points: Vec<AreaPoint> },
+
+    /// Text associated with the notice
+    Text(String),
+}
+
+/// Absolute position of a polyline or polygon point.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AreaPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl LatLon for AreaPoint {
+    fn latitude(&self) -> Option<f64> {
+        Some(self.latitude)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        Some(self.longitude)
+    }
+}
+
+/// Area notice type (IMO SN.1/Circ.289, table 11.9)
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AreaNoticeType {
+    /// Caution area: marine mammals habitat
+    CautionMarineMammalsHabitat, // 0
+
+    /// Caution area: marine mammals in area, reduce speed
+    CautionMarineMammalsReduceSpeed, // 1
+
+    /// Caution area: marine mammals in area, stay clear
+    CautionMarineMammalsStayClear, // 2
+
+    /// Caution area: marine mammals in area, report sightings
+    CautionMarineMammalsReportSightings, // 3
+
+    /// Caution area: protected habitat, reduce speed
+    CautionProtectedHabitatReduceSpeed, // 4
+
+    /// Caution area: protected habitat, stay clear
+    CautionProtectedHabitatStayClear, // 5
+
+    /// Caution area: protected habitat, no fishing or anchoring
+    CautionProtectedHabitatNoFishingOrAnchoring, // 6
+
+    /// Caution area: derelicts (drifting objects)
+    CautionDerelicts, // 7
+
+    /// Caution area: traffic congestion
+    CautionTrafficCongestion, // 8
+
+    /// Caution area: marine event
+    CautionMarineEvent, // 9
+
+    /// Caution area: divers down
+    CautionDiversDown, // 10
+
+    /// Caution area: swim area
+    CautionSwimArea, // 11
+
+    /// Caution area: dredge operations
+    CautionDredgeOperations, // 12
+
+    /// Caution area: survey operations
+    CautionSurveyOperations, // 13
+
+    /// Caution area: underwater operation
+    CautionUnderwaterOperation, // 14
+
+    /// Caution area: seaplane operations
+    CautionSeaplaneOperations, // 15
+
+    /// Caution area: fishery, nets in water
+    CautionFisheryNetsInWater, // 16
+
+    /// Caution area: cluster of fishing vessels
+    CautionClusterOfFishingVessels, // 17
+
+    /// Caution area: fairway closed
+    CautionFairwayClosed, // 18
+
+    /// Caution area: harbour closed
+    CautionHarbourClosed, // 19
+
+    /// Caution area: risk
+    CautionRisk, // 20
+
+    /// Caution area: underwater vehicle operation
+    CautionUnderwaterVehicleOperation, // 21
+
+    /// Environmental caution area: storm front (line squall)
+    EnvironmentalStormFront, // 23
+
+    /// Environmental caution area: hazardous sea ice
+    EnvironmentalHazardousSeaIce, // 24
+
+    /// Environmental caution area: storm warning (storm cell or line of storms)
+    EnvironmentalStormWarning, // 25
+
+    /// Environmental caution area: high wind
+    EnvironmentalHighWind, // 26
+
+    /// Environmental caution area: high waves
+    EnvironmentalHighWaves, // 27
+
+    /// Environmental caution area: restricted visibility (fog, rain, etc.)
+    EnvironmentalRestrictedVisibility, // 28
+
+    /// Environmental caution area: strong currents
+    EnvironmentalStrongCurrents, // 29
+
+    /// Environmental caution area: heavy icing
+    EnvironmentalHeavyIcing, // 30
+
+    /// Restricted area: fishing prohibited
+    RestrictedFishingProhibited, // 32
+
+    /// Restricted area: no anchoring
+    RestrictedNoAnchoring, // 33
+
+    /// Restricted area: entry approval required prior to transit
+    RestrictedEntryApprovalRequired, // 34
+
+    /// Restricted area: entry prohibited
+    RestrictedEntryProhibited, // 35
+
+    /// Restricted area: active military operating area
+    RestrictedActiveMilitaryOperatingArea, // 36
+
+    /// Restricted area: firing, danger area
+    RestrictedFiringDangerArea, // 37
+
+    /// Restricted area: drifting mines
+    RestrictedDriftingMines, // 38
+
+    /// Anchorage area: anchorage open
+    AnchorageOpen, // 40
+
+    /// Anchorage area: anchorage closed
+    AnchorageClosed, // 41
+
+    /// Anchorage area: anchoring prohibited
+    AnchorageAnchoringProhibited, // 42
+
+    /// Anchorage area: deep draft anchorage
+    AnchorageDeepDraft, // 43
+
+    /// Anchorage area: shallow draft anchorage
+    AnchorageShallowDraft, // 44
+
+    /// Anchorage area: vessel transfer operations
+    AnchorageVesselTransferOperations, // 45
+
+    /// Security alert: level 1
+    SecurityAlertLevel1, // 56
+
+    /// Security alert: level 2
+    SecurityAlertLevel2, // 57
+
+    /// Security alert: level 3
+    SecurityAlertLevel3, // 58
+
+    /// Distress area: vessel disabled and adrift
+    DistressVesselDisabledAndAdrift, // 64
+
+    /// Distress area: vessel sinking
+    DistressVesselSinking, // 65
+
+    /// Distress area: vessel abandoning ship
+    DistressVesselAbandoningShip, // 66
+
+    /// Distress area: vessel requests medical assistance
+    DistressVesselRequestsMedicalAssistance, // 67
+
+    /// Distress area: vessel flooding
+    DistressVesselFlooding, // 68
+
+    /// Distress area: vessel fire/explosion
+    DistressVesselFireOrExplosion, // 69
+
+    /// Distress area: vessel grounding
+    DistressVesselGrounding, // 70
+
+    /// Distress area: vessel collision
+    DistressVesselCollision, // 71
+
+    /// Distress area: vessel listing/capsizing
+    DistressVesselListingOrCapsizing, // 72
+
+    /// Distress area: vessel under assault
+    DistressVesselUnderAssault, // 73
+
+    /// Distress area: person overboard
+    DistressPersonOverboard, // 74
+
+    /// Distress area: SAR area
+    DistressSearchAndRescueArea, // 75
+
+    /// Distress area: pollution response area
+    DistressPollutionResponseArea, // 76
+
+    /// Instruction: contact VTS at this point/juncture
+    InstructionContactVts, // 80
+
+    /// Instruction: contact port administration at this point/juncture
+    InstructionContactPortAdministration, // 81
+
+    /// Instruction: do not proceed beyond this point/juncture
+    InstructionDoNotProceedBeyond, // 82
+
+    /// Instruction: await instructions prior to proceeding beyond this point/juncture
+    InstructionAwaitInstructions, // 83
+
+    /// Proceed to this location, await instructions
+    ProceedToLocation, // 84
+
+    /// Clearance granted, proceed to berth
+    ClearanceGranted, // 85
+
+    /// Information: pilot boarding position
+    InformationPilotBoardingPosition, // 88
+
+    /// Information: icebreaker waiting area
+    InformationIcebreakerWaitingArea, // 89
+
+    /// Information: places of refuge
+    InformationPlacesOfRefuge, // 90
+
+    /// Information: position of icebreakers
+    InformationPositionOfIcebreakers, // 91
+
+    /// Information: location of response units
+    InformationLocationOfResponseUnits, // 92
+
+    /// VTS active target
+    VtsActiveTarget, // 93
+
+    /// Rogue or suspicious vessel
+    RogueOrSuspiciousVessel, // 94
+
+    /// Vessel requesting non-distress assistance
+    VesselRequestingNonDistressAssistance, // 95
+
+    /// Chart feature: sunken vessel
+    ChartFeatureSunkenVessel, // 96
+
+    /// Chart feature: submerged object
+    ChartFeatureSubmergedObject, // 97
+
+    /// Chart feature: semi-submerged object
+    ChartFeatureSemiSubmergedObject, // 98
+
+    /// Chart feature: shoal area
+    ChartFeatureShoalArea, // 99
+
+    /// Chart feature: shoal area due north
+    ChartFeatureShoalAreaDueNorth, // 100
+
+    /// Chart feature: shoal area due east
+    ChartFeatureShoalAreaDueEast, // 101
+
+    /// Chart feature: shoal area due south
+    ChartFeatureShoalAreaDueSouth, // 102
+
+    /// Chart feature: shoal area due west
+    ChartFeatureShoalAreaDueWest, // 103
+
+    /// Chart feature: channel obstruction
+    ChartFeatureChannelObstruction, // 104
+
+    /// Chart feature: reduced vertical clearance
+    ChartFeatureReducedVerticalClearance, // 105
+
+    /// Chart feature: bridge closed
+    ChartFeatureBridgeClosed, // 106
+
+    /// Chart feature: bridge partially open
+    ChartFeatureBridgePartiallyOpen, // 107
+
+    /// Chart feature: bridge fully open
+    ChartFeatureBridgeFullyOpen, // 108
+
+    /// Report from ship: icing info
+    ReportIcingInfo, // 112
+
+    /// Report from ship: miscellaneous information
+    ReportMiscellaneousInformation, // 114
+
+    /// Route: recommended route
+    RouteRecommended, // 120
+
+    /// Route: alternative route
+    RouteAlternative, // 121
+
+    /// Route: recommended route through ice
+    RouteRecommendedThroughIce, // 122
+
+    /// Other
+    Other, // 125
+
+    /// Cancellation
+    Cancellation, // 126
+
+    /// Undefined
+    Undefined, // 127
+
+    /// Reserved for future use
+    Reserved(u8),
+}
+
+impl AreaNoticeType {
+    fn new(raw: u8) -> AreaNoticeType {
+        match raw {
+            0 => AreaNoticeType::CautionMarineMammalsHabitat,
+            1 => AreaNoticeType::CautionMarineMammalsReduceSpeed,
+            2 => AreaNoticeType::CautionMarineMammalsStayClear,
+            3 => AreaNoticeType::CautionMarineMammalsReportSightings,
+            4 => AreaNoticeType::CautionProtectedHabitatReduceSpeed,
+            5 => AreaNoticeType::CautionProtectedHabitatStayClear,
+            6 => AreaNoticeType::CautionProtectedHabitatNoFishingOrAnchoring,
+            7 => AreaNoticeType::CautionDerelicts,
+            8 => AreaNoticeType::CautionTrafficCongestion,
+            9 => AreaNoticeType::CautionMarineEvent,
+            10 => AreaNoticeType::CautionDiversDown,
+            11 => AreaNoticeType::CautionSwimArea,
+            12 => AreaNoticeType::CautionDredgeOperations,
+            13 => AreaNoticeType::CautionSurveyOperations,
+            14 => AreaNoticeType::CautionUnderwaterOperation,
+            15 => AreaNoticeType::CautionSeaplaneOperations,
+            16 => AreaNoticeType::CautionFisheryNetsInWater,
+            17 => AreaNoticeType::CautionClusterOfFishingVessels,
+            18 => AreaNoticeType::CautionFairwayClosed,
+            19 => AreaNoticeType::CautionHarbourClosed,
+            20 => AreaNoticeType::CautionRisk,
+            21 => AreaNoticeType::CautionUnderwaterVehicleOperation,
+            23 => AreaNoticeType::EnvironmentalStormFront,
+            24 => AreaNoticeType::EnvironmentalHazardousSeaIce,
+            25 => AreaNoticeType::EnvironmentalStormWarning,
+            26 => AreaNoticeType::EnvironmentalHighWind,
+            27 => AreaNoticeType::EnvironmentalHighWaves,
+            28 => AreaNoticeType::EnvironmentalRestrictedVisibility,
+            29 => AreaNoticeType::EnvironmentalStrongCurrents,
+            30 => AreaNoticeType::EnvironmentalHeavyIcing,
+            32 => AreaNoticeType::RestrictedFishingProhibited,
+            33 => AreaNoticeType::RestrictedNoAnchoring,
+            34 => AreaNoticeType::RestrictedEntryApprovalRequired,
+            35 => AreaNoticeType::RestrictedEntryProhibited,
+            36 => AreaNoticeType::RestrictedActiveMilitaryOperatingArea,
+            37 => AreaNoticeType::RestrictedFiringDangerArea,
+            38 => AreaNoticeType::RestrictedDriftingMines,
+            40 => AreaNoticeType::AnchorageOpen,
+            41 => AreaNoticeType::AnchorageClosed,
+            42 => AreaNoticeType::AnchorageAnchoringProhibited,
+            43 => AreaNoticeType::AnchorageDeepDraft,
+            44 => AreaNoticeType::AnchorageShallowDraft,
+            45 => AreaNoticeType::AnchorageVesselTransferOperations,
+            56 => AreaNoticeType::SecurityAlertLevel1,
+            57 => AreaNoticeType::SecurityAlertLevel2,
+            58 => AreaNoticeType::SecurityAlertLevel3,
+            64 => AreaNoticeType::DistressVesselDisabledAndAdrift,
+            65 => AreaNoticeType::DistressVesselSinking,
+            66 => AreaNoticeType::DistressVesselAbandoningShip,
+            67 => AreaNoticeType::DistressVesselRequestsMedicalAssistance,
+            68 => AreaNoticeType::DistressVesselFlooding,
+            69 => AreaNoticeType::DistressVesselFireOrExplosion,
+            70 => AreaNoticeType::DistressVesselGrounding,
+            71 => AreaNoticeType::DistressVesselCollision,
+            72 => AreaNoticeType::DistressVesselListingOrCapsizing,
+            73 => AreaNoticeType::DistressVesselUnderAssault,
+            74 => AreaNoticeType::DistressPersonOverboard,
+            75 => AreaNoticeType::DistressSearchAndRescueArea,
+            76 => AreaNoticeType::DistressPollutionResponseArea,
+            80 => AreaNoticeType::InstructionContactVts,
+            81 => AreaNoticeType::InstructionContactPortAdministration,
+            82 => AreaNoticeType::InstructionDoNotProceedBeyond,
+            83 => AreaNoticeType::InstructionAwaitInstructions,
+            84 => AreaNoticeType::ProceedToLocation,
+            85 => AreaNoticeType::ClearanceGranted,
+            88 => AreaNoticeType::InformationPilotBoardingPosition,
+            89 => AreaNoticeType::InformationIcebreakerWaitingArea,
+            90 => AreaNoticeType::InformationPlacesOfRefuge,
+            91 => AreaNoticeType::InformationPositionOfIcebreakers,
+            92 => AreaNoticeType::InformationLocationOfResponseUnits,
+            93 => AreaNoticeType::VtsActiveTarget,
+            94 => AreaNoticeType::RogueOrSuspiciousVessel,
+            95 => AreaNoticeType::VesselRequestingNonDistressAssistance,
+            96 => AreaNoticeType::ChartFeatureSunkenVessel,
+            97 => AreaNoticeType::ChartFeatureSubmergedObject,
+            98 => AreaNoticeType::ChartFeatureSemiSubmergedObject,
+            99 => AreaNoticeType::ChartFeatureShoalArea,
+            100 => AreaNoticeType::ChartFeatureShoalAreaDueNorth,
+            101 => AreaNoticeType::ChartFeatureShoalAreaDueEast,
+            102 => AreaNoticeType::ChartFeatureShoalAreaDueSouth,
+            103 => AreaNoticeType::ChartFeatureShoalAreaDueWest,
+            104 => AreaNoticeType::ChartFeatureChannelObstruction,
+            105 => AreaNoticeType::ChartFeatureReducedVerticalClearance,
+            106 => AreaNoticeType::ChartFeatureBridgeClosed,
+            107 => AreaNoticeType::ChartFeatureBridgePartiallyOpen,
+            108 => AreaNoticeType::ChartFeatureBridgeFullyOpen,
+            112 => AreaNoticeType::ReportIcingInfo,
+            114 => AreaNoticeType::ReportMiscellaneousInformation,
+            120 => AreaNoticeType::RouteRecommended,
+            121 => AreaNoticeType::RouteAlternative,
+            122 => AreaNoticeType::RouteRecommendedThroughIce,
+            125 => AreaNoticeType::Other,
+            126 => AreaNoticeType::Cancellation,
+            127 => AreaNoticeType::Undefined,
+            _ => AreaNoticeType::Reserved(raw),
+        }
+    }
+
+    pub fn to_value(&self) -> u8 {
+        match self {
+            AreaNoticeType::CautionMarineMammalsHabitat => 0,
+            AreaNoticeType::CautionMarineMammalsReduceSpeed => 1,
+            AreaNoticeType::CautionMarineMammalsStayClear => 2,
+            AreaNoticeType::CautionMarineMammalsReportSightings => 3,
+            AreaNoticeType::CautionProtectedHabitatReduceSpeed => 4,
+            AreaNoticeType::CautionProtectedHabitatStayClear => 5,
+            AreaNoticeType::CautionProtectedHabitatNoFishingOrAnchoring => 6,
+            AreaNoticeType::CautionDerelicts => 7,
+            AreaNoticeType::CautionTrafficCongestion => 8,
+            AreaNoticeType::CautionMarineEvent => 9,
+            AreaNoticeType::CautionDiversDown => 10,
+            AreaNoticeType::CautionSwimArea => 11,
+            AreaNoticeType::CautionDredgeOperations => 12,
+            AreaNoticeType::CautionSurveyOperations => 13,
+            AreaNoticeType::CautionUnderwaterOperation => 14,
+            AreaNoticeType::CautionSeaplaneOperations => 15,
+            AreaNoticeType::CautionFisheryNetsInWater => 16,
+            AreaNoticeType::CautionClusterOfFishingVessels => 17,
+            AreaNoticeType::CautionFairwayClosed => 18,
+            AreaNoticeType::CautionHarbourClosed => 19,
+            AreaNoticeType::CautionRisk => 20,
+            AreaNoticeType::CautionUnderwaterVehicleOperation => 21,
+            AreaNoticeType::EnvironmentalStormFront => 23,
+            AreaNoticeType::EnvironmentalHazardousSeaIce => 24,
+            AreaNoticeType::EnvironmentalStormWarning => 25,
+            AreaNoticeType::EnvironmentalHighWind => 26,
+            AreaNoticeType::EnvironmentalHighWaves => 27,
+            AreaNoticeType::EnvironmentalRestrictedVisibility => 28,
+            AreaNoticeType::EnvironmentalStrongCurrents => 29,
+            AreaNoticeType::EnvironmentalHeavyIcing => 30,
+            AreaNoticeType::RestrictedFishingProhibited => 32,
+            AreaNoticeType::RestrictedNoAnchoring => 33,
+            AreaNoticeType::RestrictedEntryApprovalRequired => 34,
+            AreaNoticeType::RestrictedEntryProhibited => 35,
+            AreaNoticeType::RestrictedActiveMilitaryOperatingArea => 36,
+            AreaNoticeType::RestrictedFiringDangerArea => 37,
+            AreaNoticeType::RestrictedDriftingMines => 38,
+            AreaNoticeType::AnchorageOpen => 40,
+            AreaNoticeType::AnchorageClosed => 41,
+            AreaNoticeType::AnchorageAnchoringProhibited => 42,
+            AreaNoticeType::AnchorageDeepDraft => 43,
+            AreaNoticeType::AnchorageShallowDraft => 44,
+            AreaNoticeType::AnchorageVesselTransferOperations => 45,
+            AreaNoticeType::SecurityAlertLevel1 => 56,
+            AreaNoticeType::SecurityAlertLevel2 => 57,
+            AreaNoticeType::SecurityAlertLevel3 => 58,
+            AreaNoticeType::DistressVesselDisabledAndAdrift => 64,
+            AreaNoticeType::DistressVesselSinking => 65,
+            AreaNoticeType::DistressVesselAbandoningShip => 66,
+            AreaNoticeType::DistressVesselRequestsMedicalAssistance => 67,
+            AreaNoticeType::DistressVesselFlooding => 68,
+            AreaNoticeType::DistressVesselFireOrExplosion => 69,
+            AreaNoticeType::DistressVesselGrounding => 70,
+            AreaNoticeType::DistressVesselCollision => 71,
+            AreaNoticeType::DistressVesselListingOrCapsizing => 72,
+            AreaNoticeType::DistressVesselUnderAssault => 73,
+            AreaNoticeType::DistressPersonOverboard => 74,
+            AreaNoticeType::DistressSearchAndRescueArea => 75,
+            AreaNoticeType::DistressPollutionResponseArea => 76,
+            AreaNoticeType::InstructionContactVts => 80,
+            AreaNoticeType::InstructionContactPortAdministration => 81,
+            AreaNoticeType::InstructionDoNotProceedBeyond => 82,
+            AreaNoticeType::InstructionAwaitInstructions => 83,
+            AreaNoticeType::ProceedToLocation => 84,
+            AreaNoticeType::ClearanceGranted => 85,
+            AreaNoticeType::InformationPilotBoardingPosition => 88,
+            AreaNoticeType::InformationIcebreakerWaitingArea => 89,
+            AreaNoticeType::InformationPlacesOfRefuge => 90,
+            AreaNoticeType::InformationPositionOfIcebreakers => 91,
+            AreaNoticeType::InformationLocationOfResponseUnits => 92,
+            AreaNoticeType::VtsActiveTarget => 93,
+            AreaNoticeType::RogueOrSuspiciousVessel => 94,
+            AreaNoticeType::VesselRequestingNonDistressAssistance => 95,
+            AreaNoticeType::ChartFeatureSunkenVessel => 96,
+            AreaNoticeType::ChartFeatureSubmergedObject => 97,
+            AreaNoticeType::ChartFeatureSemiSubmergedObject => 98,
+            AreaNoticeType::ChartFeatureShoalArea => 99,
+            AreaNoticeType::ChartFeatureShoalAreaDueNorth => 100,
+            AreaNoticeType::ChartFeatureShoalAreaDueEast => 101,
+            AreaNoticeType::ChartFeatureShoalAreaDueSouth => 102,
+            AreaNoticeType::ChartFeatureShoalAreaDueWest => 103,
+            AreaNoticeType::ChartFeatureChannelObstruction => 104,
+            AreaNoticeType::ChartFeatureReducedVerticalClearance => 105,
+            AreaNoticeType::ChartFeatureBridgeClosed => 106,
+            AreaNoticeType::ChartFeatureBridgePartiallyOpen => 107,
+            AreaNoticeType::ChartFeatureBridgeFullyOpen => 108,
+            AreaNoticeType::ReportIcingInfo => 112,
+            AreaNoticeType::ReportMiscellaneousInformation => 114,
+            AreaNoticeType::RouteRecommended => 120,
+            AreaNoticeType::RouteAlternative => 121,
+            AreaNoticeType::RouteRecommendedThroughIce => 122,
+            AreaNoticeType::Other => 125,
+            AreaNoticeType::Cancellation => 126,
+            AreaNoticeType::Undefined => 127,
+            AreaNoticeType::Reserved(raw) => *raw,
+        }
+    }
+}
+
+impl Default for AreaNoticeType {
+    fn default() -> AreaNoticeType {
+        AreaNoticeType::Undefined
+    }
+}
+
+impl std::fmt::Display for AreaNoticeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AreaNoticeType::CautionMarineMammalsHabitat => {
+                write!(f, "caution area: marine mammals habitat")
+            }
+            AreaNoticeType::CautionMarineMammalsReduceSpeed => {
+                write!(f, "caution area: marine mammals in area, reduce speed")
+            }
+            AreaNoticeType::CautionMarineMammalsStayClear => {
+                write!(f, "caution area: marine mammals in area, stay clear")
+            }
+            AreaNoticeType::CautionMarineMammalsReportSightings => {
+                write!(f, "caution area: marine mammals in area, report sightings")
+            }
+            AreaNoticeType::CautionProtectedHabitatReduceSpeed => {
+                write!(f, "caution area: protected habitat, reduce speed")
+            }
+            AreaNoticeType::CautionProtectedHabitatStayClear => {
+                write!(f, "caution area: protected habitat, stay clear")
+            }
+            AreaNoticeType::CautionProtectedHabitatNoFishingOrAnchoring => write!(
+                f,
+                "caution area: protected habitat, no fishing or anchoring"
+            ),
+            AreaNoticeType::CautionDerelicts => {
+                write!(f, "caution area: derelicts (drifting objects)")
+            }
+            AreaNoticeType::CautionTrafficCongestion => {
+                write!(f, "caution area: traffic congestion")
+            }
+            AreaNoticeType::CautionMarineEvent => write!(f, "caution area: marine event"),
+            AreaNoticeType::CautionDiversDown => write!(f, "caution area: divers down"),
+            AreaNoticeType::CautionSwimArea => write!(f, "caution area: swim area"),
+            AreaNoticeType::CautionDredgeOperations => write!(f, "caution area: dredge operations"),
+            AreaNoticeType::CautionSurveyOperations => write!(f, "caution area: survey operations"),
+            AreaNoticeType::CautionUnderwaterOperation => {
+                write!(f, "caution area: underwater operation")
+            }
+            AreaNoticeType::CautionSeaplaneOperations => {
+                write!(f, "caution area: seaplane operations")
+            }
+            AreaNoticeType::CautionFisheryNetsInWater => {
+                write!(f, "caution area: fishery, nets in water")
+            }
+            AreaNoticeType::CautionClusterOfFishingVessels => {
+                write!(f, "caution area: cluster of fishing vessels")
+            }
+            AreaNoticeType::CautionFairwayClosed => write!(f, "caution area: fairway closed"),
+            AreaNoticeType::CautionHarbourClosed => write!(f, "caution area: harbour closed"),
+            AreaNoticeType::CautionRisk => write!(f, "caution area: risk"),
+            AreaNoticeType::CautionUnderwaterVehicleOperation => {
+                write!(f, "caution area: underwater vehicle operation")
+            }
+            AreaNoticeType::EnvironmentalStormFront => {
+                write!(f, "environmental caution area: storm front (line squall)")
+            }
+            AreaNoticeType::EnvironmentalHazardousSeaIce => {
+                write!(f, "environmental caution area: hazardous sea ice")
+            }
+            AreaNoticeType::EnvironmentalStormWarning => write!(
+                f,
+                "environmental caution area: storm warning (storm cell or line of storms)"
+            ),
+            AreaNoticeType::EnvironmentalHighWind => {
+                write!(f, "environmental caution area: high wind")
+            }
+            AreaNoticeType::EnvironmentalHighWaves => {
+                write!(f, "environmental caution area: high waves")
+            }
+            AreaNoticeType::EnvironmentalRestrictedVisibility => write!(
+                f,
+                "environmental caution area: restricted visibility (fog, rain, etc.)"
+            ),
+            AreaNoticeType::EnvironmentalStrongCurrents => {
+                write!(f, "environmental caution area: strong currents")
+            }
+            AreaNoticeType::EnvironmentalHeavyIcing => {
+                write!(f, "environmental caution area: heavy icing")
+            }
+            AreaNoticeType::RestrictedFishingProhibited => {
+                write!(f, "restricted area: fishing prohibited")
+            }
+            AreaNoticeType::RestrictedNoAnchoring => write!(f, "restricted area: no anchoring"),
+            AreaNoticeType::RestrictedEntryApprovalRequired => write!(
+                f,
+                "restricted area: entry approval required prior to transit"
+            ),
+            AreaNoticeType::RestrictedEntryProhibited => {
+                write!(f, "restricted area: entry prohibited")
+            }
+            AreaNoticeType::RestrictedActiveMilitaryOperatingArea => {
+                write!(f, "restricted area: active military operating area")
+            }
+            AreaNoticeType::RestrictedFiringDangerArea => {
+                write!(f, "restricted area: firing, danger area")
+            }
+            AreaNoticeType::RestrictedDriftingMines => write!(f, "restricted area: drifting mines"),
+            AreaNoticeType::AnchorageOpen => write!(f, "anchorage area: anchorage open"),
+            AreaNoticeType::AnchorageClosed => write!(f, "anchorage area: anchorage closed"),
+            AreaNoticeType::AnchorageAnchoringProhibited => {
+                write!(f, "anchorage area: anchoring prohibited")
+            }
+            AreaNoticeType::AnchorageDeepDraft => write!(f, "anchorage area: deep draft anchorage"),
+            AreaNoticeType::AnchorageShallowDraft => {
+                write!(f, "anchorage area: shallow draft anchorage")
+            }
+            AreaNoticeType::AnchorageVesselTransferOperations => {
+                write!(f, "anchorage area: vessel transfer operations")
+            }
+            AreaNoticeType::SecurityAlertLevel1 => write!(f, "security alert: level 1"),
+            AreaNoticeType::SecurityAlertLevel2 => write!(f, "security alert: level 2"),
+            AreaNoticeType::SecurityAlertLevel3 => write!(f, "security alert: level 3"),
+            AreaNoticeType::DistressVesselDisabledAndAdrift => {
+                write!(f, "distress area: vessel disabled and adrift")
+            }
+            AreaNoticeType::DistressVesselSinking => write!(f, "distress area: vessel sinking"),
+            AreaNoticeType::DistressVesselAbandoningShip => {
+                write!(f, "distress area: vessel abandoning ship")
+            }
+            AreaNoticeType::DistressVesselRequestsMedicalAssistance => {
+                write!(f, "distress area: vessel requests medical assistance")
+            }
+            AreaNoticeType::DistressVesselFlooding => write!(f, "distress area: vessel flooding"),
+            AreaNoticeType::DistressVesselFireOrExplosion => {
+                write!(f, "distress area: vessel fire/explosion")
+            }
+            AreaNoticeType::DistressVesselGrounding => write!(f, "distress area: vessel grounding"),
+            AreaNoticeType::DistressVesselCollision => write!(f, "distress area: vessel collision"),
+            AreaNoticeType::DistressVesselListingOrCapsizing => {
+                write!(f, "distress area: vessel listing/capsizing")
+            }
+            AreaNoticeType::DistressVesselUnderAssault => {
+                write!(f, "distress area: vessel under assault")
+            }
+            AreaNoticeType::DistressPersonOverboard => write!(f, "distress area: person overboard"),
+            AreaNoticeType::DistressSearchAndRescueArea => write!(f, "distress area: SAR area"),
+            AreaNoticeType::DistressPollutionResponseArea => {
+                write!(f, "distress area: pollution response area")
+            }
+            AreaNoticeType::InstructionContactVts => {
+                write!(f, "instruction: contact VTS at this point/juncture")
+            }
+            AreaNoticeType::InstructionContactPortAdministration => write!(
+                f,
+                "instruction: contact port administration at this point/juncture"
+            ),
+            AreaNoticeType::InstructionDoNotProceedBeyond => {
+                write!(f, "instruction: do not proceed beyond this point/juncture")
+            }
+            AreaNoticeType::InstructionAwaitInstructions => write!(
+                f,
+                "instruction: await instructions prior to proceeding beyond this point/juncture"
+            ),
+            AreaNoticeType::ProceedToLocation => {
+                write!(f, "proceed to this location, await instructions")
+            }
+            AreaNoticeType::ClearanceGranted => write!(f, "clearance granted, proceed to berth"),
+            AreaNoticeType::InformationPilotBoardingPosition => {
+                write!(f, "information: pilot boarding position")
+            }
+            AreaNoticeType::InformationIcebreakerWaitingArea => {
+                write!(f, "information: icebreaker waiting area")
+            }
+            AreaNoticeType::InformationPlacesOfRefuge => write!(f, "information: places of refuge"),
+            AreaNoticeType::InformationPositionOfIcebreakers => {
+                write!(f, "information: position of icebreakers")
+            }
+            AreaNoticeType::InformationLocationOfResponseUnits => {
+                write!(f, "information: location of response units")
+            }
+            AreaNoticeType::VtsActiveTarget => write!(f, "VTS active target"),
+            AreaNoticeType::RogueOrSuspiciousVessel => write!(f, "rogue or suspicious vessel"),
+            AreaNoticeType::VesselRequestingNonDistressAssistance => {
+                write!(f, "vessel requesting non-distress assistance")
+            }
+            AreaNoticeType::ChartFeatureSunkenVessel => write!(f, "chart feature: sunken vessel"),
+            AreaNoticeType::ChartFeatureSubmergedObject => {
+                write!(f, "chart feature: submerged object")
+            }
+            AreaNoticeType::ChartFeatureSemiSubmergedObject => {
+                write!(f, "chart feature: semi-submerged object")
+            }
+            AreaNoticeType::ChartFeatureShoalArea => write!(f, "chart feature: shoal area"),
+            AreaNoticeType::ChartFeatureShoalAreaDueNorth => {
+                write!(f, "chart feature: shoal area due north")
+            }
+            AreaNoticeType::ChartFeatureShoalAreaDueEast => {
+                write!(f, "chart feature: shoal area due east")
+            }
+            AreaNoticeType::ChartFeatureShoalAreaDueSouth => {
+                write!(f, "chart feature: shoal area due south")
+            }
+            AreaNoticeType::ChartFeatureShoalAreaDueWest => {
+                write!(f, "chart feature: shoal area due west")
+            }
+            AreaNoticeType::ChartFeatureChannelObstruction => {
+                write!(f, "chart feature: channel obstruction")
+            }
+            AreaNoticeType::ChartFeatureReducedVerticalClearance => {
+                write!(f, "chart feature: reduced vertical clearance")
+            }
+            AreaNoticeType::ChartFeatureBridgeClosed => write!(f, "chart feature: bridge closed"),
+            AreaNoticeType::ChartFeatureBridgePartiallyOpen => {
+                write!(f, "chart feature: bridge partially open")
+            }
+            AreaNoticeType::ChartFeatureBridgeFullyOpen => {
+                write!(f, "chart feature: bridge fully open")
+            }
+            AreaNoticeType::ReportIcingInfo => write!(f, "report from ship: icing info"),
+            AreaNoticeType::ReportMiscellaneousInformation => {
+                write!(f, "report from ship: miscellaneous information")
+            }
+            AreaNoticeType::RouteRecommended => write!(f, "route: recommended route"),
+            AreaNoticeType::RouteAlternative => write!(f, "route: alternative route"),
+            AreaNoticeType::RouteRecommendedThroughIce => {
+                write!(f, "route: recommended route through ice")
+            }
+            AreaNoticeType::Other => write!(f, "other"),
+            AreaNoticeType::Cancellation => write!(f, "cancellation"),
+            AreaNoticeType::Undefined => write!(f, "undefined"),
+            AreaNoticeType::Reserved(_) => write!(f, "(reserved)"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decode area notice from the application data of a binary message. Bit indices are relative to
+/// the start of the application data. Returns `None` if the data doesn't contain any sub-areas
+/// or all the sub-areas have reserved shapes.
+pub(crate) fn decode(bv: &BitVec, now: DateTime<Utc>) -> Option<AreaNotice> {
+    if bv.len() < HEADER_LENGTH + SUB_AREA_LENGTH {
+        return None;
+    }
+
+    let mut sub_areas: Vec<AreaNoticeSubArea> = Vec::new();
+    let mut origin: Option<(f64, f64)> = None;
+    let count = ((bv.len() - HEADER_LENGTH) / SUB_AREA_LENGTH).min(MAX_SUB_AREAS);
+    for i in 0..count {
+        let index = HEADER_LENGTH + i * SUB_AREA_LENGTH;
+        let sub_area = decode_sub_area(bv, index, &mut origin);
+        match (sub_areas.last_mut(), sub_area) {
+            (
+                Some(AreaNoticeSubArea::Polyline { points }),
+                Some(AreaNoticeSubArea::Polyline { points: more }),
+            )
+            | (
+                Some(AreaNoticeSubArea::Polygon { points }),
+                Some(AreaNoticeSubArea::Polygon { points: more }),
+            ) => {
+                points.extend(more);
+            }
+            (Some(AreaNoticeSubArea::Text(text)), Some(AreaNoticeSubArea::Text(more))) => {
+                text.push_str(&more);
+            }
+            (_, Some(sub_area)) => {
+                sub_areas.push(sub_area);
+            }
+            (_, None) => {}
+        }
+    }
+    if sub_areas.is_empty() {
+        return None;
+    }
+
+    Some(AreaNotice {
+        message_linkage_id: pick_u64(bv, 0, 10) as u16,
+        notice_type: AreaNoticeType::new(pick_u64(bv, 10, 7) as u8),
+        start_time: pick_nearest_date_time(bv, 17, now),
+        duration_minutes: {
+            let raw = pick_u64(bv, 37, 18) as u32;
+            if raw != 262143 {
+                Some(raw)
+            } else {
+                None
+            }
+        },
+        sub_areas,
+    })
+}
+
+/// Decode one sub-area starting at the given index. The `origin` is the latest position which
+/// polylines and polygons are relative to. Returns `None` for reserved shapes.
+fn decode_sub_area(
+    bv: &BitVec,
+    index: usize,
+    origin: &mut Option<(f64, f64)>,
+) -> Option<AreaNoticeSubArea> {
+    let shape = pick_u64(bv, index, 3);
+    let scale = scale_factor(pick_u64(bv, index + 3, 2) as u8);
+    let position = || {
+        let precision = pick_u64(bv, index + 54, 3) as u8;
+        (
+            round_to_precision(pick_latitude_1000(bv, index + 30), precision),
+            round_to_precision(pick_longitude_1000(bv, index + 5), precision),
+        )
+    };
+    match shape {
+        0 => {
+            let (latitude, longitude) = position();
+            *origin = latitude.zip(longitude);
+            Some(AreaNoticeSubArea::Circle {
+                latitude,
+                longitude,
+                radius_m: pick_u64(bv, index + 57, 12) as u32 * scale,
+            })
+        }
+        1 => {
+            let (latitude, longitude) = position();
+            *origin = latitude.zip(longitude);
+            Some(AreaNoticeSubArea::Rectangle {
+                latitude,
+                longitude,
+                east_dimension_m: pick_u64(bv, index + 57, 8) as u32 * scale,
+                north_dimension_m: pick_u64(bv, index + 65, 8) as u32 * scale,
+                orientation: pick_u64(bv, index + 73, 9) as u16,
+            })
+        }
+        2 => {
+            let (latitude, longitude) = position();
+            *origin = latitude.zip(longitude);
+            Some(AreaNoticeSubArea::Sector {
+                latitude,
+                longitude,
+                radius_m: pick_u64(bv, index + 57, 12) as u32 * scale,
+                left_boundary: pick_u64(bv, index + 69, 9) as u16,
+                right_boundary: pick_u64(bv, index + 78, 9) as u16,
+            })
+        }
+        3 => Some(AreaNoticeSubArea::Polyline {
+            points: pick_points(bv, index + 5, scale, origin),
+        }),
+        4 => Some(AreaNoticeSubArea::Polygon {
+            points: pick_points(bv, index + 5, scale, origin),
+        }),
+        5 => Some(AreaNoticeSubArea::Text(pick_string(bv, index + 3, 14))),
+        _ => None,
+    }
+}
+
+/// Pick up to four polyline or polygon points given as bearing (0.5 degrees) and distance from
+/// the previous point. Points can't be resolved if there is no preceding position.
+fn pick_points(
+    bv: &BitVec,
+    index: usize,
+    scale: u32,
+    origin: &mut Option<(f64, f64)>,
+) -> Vec<AreaPoint> {
+    let mut points = Vec::new();
+    for i in 0..4 {
+        let angle = pick_u64(bv, index + i * 20, 10);
+        let distance = pick_u64(bv, index + i * 20 + 10, 10) as u32 * scale;
+        if angle >= 720 {
+            break;
+        }
+        if let Some((lat, lon)) = *origin {
+            let (lat, lon) =
+                crate::geodesy::destination_point(lat, lon, angle as f64 / 2.0, distance as f64);
+            *origin = Some((lat, lon));
+            points.push(AreaPoint {
+                latitude: lat,
+                longitude: lon,
+            });
+        }
+    }
+    points
+}
+
+/// Scale factor of distances in sub-areas.
+fn scale_factor(raw: u8) -> u32 {
+    match raw {
+        0 => 1,
+        1 => 10,
+        2 => 100,
+        _ => 1000,
+    }
+}
+
+/// Round coordinate to the given number of decimal places of minutes. Reserved precision values
+/// leave the coordinate unchanged.
+fn round_to_precision(deg: Option<f64>, precision: u8) -> Option<f64> {
+    if precision > 4 {
+        return deg;
+    }
+    let f = 10f64.powi(precision as i32);
+    deg.map(|d| (d * 60.0 * f).round() / f / 60.0)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_dac1_fid22() {
+        let mut p = NmeaParser::new();
+        p.set_clock(Clock::Fixed(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
+
+        let s1 = "!AIVDM,2,1,3,A,802<Hnh0EPbAdu000?0@eWJQf9Wr0j00005dsD=i<wP00003Rl0`00:e00;@,0*6F";
+        let s2 = "!AIVDM,2,2,3,A,00M3P2nP05`01J0010eWJQf9Wsj6@FP`ajRC<22Aq18A:U54000000000000,0*10";
+
+        // Process sentence 1
+        match p.parse_sentence(s1) {
+            Ok(_) => {}
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+
+        // Process sentence 2
+        match p.parse_sentence(s2) {
+            Ok(ps) => match ps {
+                // The expected result
+                ParsedMessage::BinaryBroadcastMessage(bbm) => {
+                    assert_eq!(bbm.mmsi, 2300123);
+                    assert_eq!(bbm.dac, 1);
+                    assert_eq!(bbm.fid, 22);
+                    match bbm.application {
+                        Some(BinaryApplication::AreaNotice(an)) => {
+                            assert_eq!(an.message_linkage_id, 42);
+                            assert_eq!(an.notice_type, AreaNoticeType::RestrictedEntryProhibited);
+                            assert_eq!(an.notice_type.to_value(), 35);
+                            assert_eq!(
+                                an.notice_type.to_string(),
+                                "restricted area: entry prohibited"
+                            );
+                            assert_eq!(an.start_time, Some(Utc.ymd(2021, 6, 15).and_hms(8, 0, 0)));
+                            assert_eq!(an.duration_minutes, Some(120));
+                            assert_eq!(an.sub_areas.len(), 5);
+
+                            // Circle with position precision of 0.01 minutes
+                            match &an.sub_areas[0] {
+                                AreaNoticeSubArea::Circle {
+                                    latitude,
+                                    longitude,
+                                    radius_m,
+                                } => {
+                                    assert::close(latitude.unwrap_or(0.0), 3609.41 / 60.0, 1e-9);
+                                    assert::close(longitude.unwrap_or(0.0), 1494.74 / 60.0, 1e-9);
+                                    assert_eq!(*radius_m, 500);
+                                }
+                                _ => {
+                                    assert!(false);
+                                }
+                            }
+
+                            // Continued polylines combined and resolved relative to the point
+                            match &an.sub_areas[2] {
+                                AreaNoticeSubArea::Polyline { points } => {
+                                    assert_eq!(points.len(), 3);
+                                    assert::close(points[0].latitude, 60.156782, 0.000001);
+                                    assert::close(points[0].longitude, 24.930405, 0.000001);
+                                    assert::close(points[1].latitude, 60.165775, 0.000001);
+                                    assert::close(points[1].longitude, 24.930405, 0.000001);
+                                    assert::close(points[2].latitude, 60.165775, 0.000001);
+                                    assert::close(points[2].longitude, 24.921367, 0.000001);
+                                }
+                                _ => {
+                                    assert!(false);
+                                }
+                            }
+
+                            match &an.sub_areas[3] {
+                                AreaNoticeSubArea::Rectangle {
+                                    east_dimension_m,
+                                    north_dimension_m,
+                                    orientation,
+                                    ..
+                                } => {
+                                    assert_eq!(*east_dimension_m, 200);
+                                    assert_eq!(*north_dimension_m, 100);
+                                    assert_eq!(*orientation, 45);
+                                }
+                                _ => {
+                                    assert!(false);
+                                }
+                            }
+
+                            assert_eq!(
+                                an.sub_areas[4],
+                                AreaNoticeSubArea::Text("ENTRY PROHIBITED".into())
+                            );
+                        }
+                        _ => {
+                            assert!(false);
+                        }
+                    }
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_reserved_shapes() {
+        let now = Utc.ymd(2021, 5, 1).and_hms(12, 0, 0);
+        let mut bv = BitVec::new();
+        bv.resize(HEADER_LENGTH + 2 * SUB_AREA_LENGTH, false);
+
+        // Circle and a sub-area of reserved shape 7
+        for i in 0..3 {
+            bv.set(HEADER_LENGTH + SUB_AREA_LENGTH + i, true);
+        }
+        match decode(&bv, now) {
+            Some(an) => assert_eq!(an.sub_areas.len(), 1),
+            None => assert!(false),
+        }
+
+        // Only reserved shapes
+        for i in 0..3 {
+            bv.set(HEADER_LENGTH + i, true);
+        }
+        assert_eq!(decode(&bv, now), None);
+    }
+}
@@ -0,0 +1,381 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! DAC 1, FID 31: Meteorological and hydrographic data (IMO SN.1/Circ.289)
+
+use super::*;
+use application::{pick_latitude_1000, pick_longitude_1000, pick_u64_max};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Length of the application data in bits
+const DATA_LENGTH: usize = 304;
+
+/// Meteorological and hydrographic data broadcast in AIS type 8 message with DAC 1 and FID 31.
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeteorologicalHydrographicData {
+    /// Longitude of the station
+    pub longitude: Option<f64>,
+
+    /// Latitude of the station
+    pub latitude: Option<f64>,
+
+    /// Position accuracy: true = high (<= 10 m), false = low (> 10 m)
+    pub high_position_accuracy: bool,
+
+    /// Day of the observation (1-31, UTC)
+    pub utc_day: Option<u8>,
+
+    /// Hour of the observation (0-23, UTC)
+    pub utc_hour: Option<u8>,
+
+    /// Minute of the observation (0-59, UTC)
+    pub utc_minute: Option<u8>,
+
+    /// Average wind speed of the last 10 minutes in knots
+    pub wind_speed_knots: Option<u8>,
+
+    /// Wind gust speed of the last 10 minutes in knots
+    pub wind_gust_knots: Option<u8>,
+
+    /// Wind direction in degrees
+    pub wind_direction: Option<u16>,
+
+    /// Wind gust direction in degrees
+    pub wind_gust_direction: Option<u16>,
+
+    /// Dry bulb air temperature in degrees Celsius
+    pub air_temperature: Option<f64>,
+
+    /// Relative humidity in percents
+    pub relative_humidity: Option<u8>,
+
+    /// Dew point in degrees Celsius
+    pub dew_point: Option<f64>,
+
+    /// Air pressure in hPa. Values 799 and 1201 mean 799 hPa or less and 1201 hPa or more.
+    pub air_pressure: Option<u16>,
+
+    /// Air pressure tendency
+    pub air_pressure_tendency: Option<Tendency>,
+
+    /// Horizontal visibility in nautical miles
+    pub visibility_nm: Option<f64>,
+
+    /// True if the visibility is greater than the value of `visibility_nm`
+    pub visibility_greater_than: bool,
+
+    /// Water level including tide as deviation from local chart datum in metres
+    pub water_level: Option<f64>,
+
+    /// Water level trend
+    pub water_level_trend: Option<Tendency>,
+
+    /// Surface current
+    pub surface_current: CurrentData,
+
+    /// Current at the depth given in `CurrentData::depth`
+    pub current_2: CurrentData,
+
+    /// Current at the depth given in `CurrentData::depth`
+    pub current_3: CurrentData,
+
+    /// Significant wave height in metres
+    pub wave_height: Option<f64>,
+
+    /// Wave period in seconds
+    pub wave_period: Option<u8>,
+
+    /// Wave direction in degrees
+    pub wave_direction: Option<u16>,
+
+    /// Swell height in metres
+    pub swell_height: Option<f64>,
+
+    /// Swell period in seconds
+    pub swell_period: Option<u8>,
+
+    /// Swell direction in degrees
+    pub swell_direction: Option<u16>,
+
+    /// Sea state according to Beaufort scale (0-12)
+    pub sea_state: Option<u8>,
+
+    /// Water temperature in degrees Celsius
+    pub water_temperature: Option<f64>,
+
+    /// Type of precipitation
+    pub precipitation: Option<PrecipitationType>,
+
+    /// Salinity in parts per thousand
+    pub salinity: Option<f64>,
+
+    /// Ice: true = yes, false = no
+    pub ice: Option<bool>,
+}
+
+impl LatLon for MeteorologicalHydrographicData {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+/// Current speed, direction and measurement depth.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurrentData {
+    /// Current speed in knots
+    pub speed_knots: Option<f64>,
+
+    /// Current direction in degrees
+    pub direction: Option<u16>,
+
+    /// Measurement depth in metres, `None` for surface current
+    pub depth: Option<u8>,
+}
+
+/// Tendency of air pressure or water level
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tendency {
+    Steady,
+    Decreasing,
+    Increasing,
+}
+
+impl Tendency {
+    fn new(raw: u8) -> Option<Tendency> {
+        match raw {
+            0 => Some(Tendency::Steady),
+            1 => Some(Tendency::Decreasing),
+            2 => Some(Tendency::Increasing),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Tendency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tendency::Steady => write!(f, "steady"),
+            Tendency::Decreasing => write!(f, "decreasing"),
+            Tendency::Increasing => write!(f, "increasing"),
+        }
+    }
+}
+
+/// Type of precipitation (WMO code table 4.201)
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrecipitationType {
+    Rain,
+    Thunderstorm,
+    FreezingRain,
+    MixedOrIce,
+    Snow,
+}
+
+impl PrecipitationType {
+    fn new(raw: u8) -> Option<PrecipitationType> {
+        match raw {
+            1 => Some(PrecipitationType::Rain),
+            2 => Some(PrecipitationType::Thunderstorm),
+            3 => Some(PrecipitationType::FreezingRain),
+            4 => Some(PrecipitationType::MixedOrIce),
+            5 => Some(PrecipitationType::Snow),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PrecipitationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrecipitationType::Rain => write!(f, "rain"),
+            PrecipitationType::Thunderstorm => write!(f, "thunderstorm"),
+            PrecipitationType::FreezingRain => write!(f, "freezing rain"),
+            PrecipitationType::MixedOrIce => write!(f, "mixed/ice"),
+            PrecipitationType::Snow => write!(f, "snow"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decode meteorological and hydrographic data from the application data of a binary message.
+/// Bit indices are relative to the start of the application data. Returns `None` if the data is
+/// too short.
+pub(crate) fn decode(bv: &BitVec) -> Option<MeteorologicalHydrographicData> {
+    if bv.len() < DATA_LENGTH {
+        return None;
+    }
+    Some(MeteorologicalHydrographicData {
+        longitude: pick_longitude_1000(bv, 0),
+        latitude: pick_latitude_1000(bv, 25),
+        high_position_accuracy: pick_u64(bv, 49, 1) != 0,
+        utc_day: {
+            let raw = pick_u64(bv, 50, 5) as u8;
+            if raw != 0 {
+                Some(raw)
+            } else {
+                None
+            }
+        },
+        utc_hour: pick_u64_max(bv, 55, 5, 23).map(|v| v as u8),
+        utc_minute: pick_u64_max(bv, 60, 6, 59).map(|v| v as u8),
+        wind_speed_knots: pick_u64_max(bv, 66, 7, 126).map(|v| v as u8),
+        wind_gust_knots: pick_u64_max(bv, 73, 7, 126).map(|v| v as u8),
+        wind_direction: pick_u64_max(bv, 80, 9, 359).map(|v| v as u16),
+        wind_gust_direction: pick_u64_max(bv, 89, 9, 359).map(|v| v as u16),
+        air_temperature: {
+            let raw = pick_i64(bv, 98, 11);
+            if (-600..=600).contains(&raw) {
+                Some(raw as f64 / 10.0)
+            } else {
+                None
+            }
+        },
+        relative_humidity: pick_u64_max(bv, 109, 7, 100).map(|v| v as u8),
+        dew_point: {
+            let raw = pick_i64(bv, 116, 10);
+            if (-200..=500).contains(&raw) {
+                Some(raw as f64 / 10.0)
+            } else {
+                None
+            }
+        },
+        air_pressure: pick_u64_max(bv, 126, 9, 402).map(|v| v as u16 + 799),
+        air_pressure_tendency: Tendency::new(pick_u64(bv, 135, 2) as u8),
+        visibility_nm: pick_u64_max(bv, 138, 7, 126).map(|v| v as f64 / 10.0),
+        visibility_greater_than: pick_u64(bv, 137, 1) != 0,
+        water_level: pick_u64_max(bv, 145, 12, 4000).map(|v| v as f64 / 100.0 - 10.0),
+        water_level_trend: Tendency::new(pick_u64(bv, 157, 2) as u8),
+        surface_current: CurrentData {
+            speed_knots: pick_u64_max(bv, 159, 8, 251).map(|v| v as f64 / 10.0),
+            direction: pick_u64_max(bv, 167, 9, 359).map(|v| v as u16),
+            depth: None,
+        },
+        current_2: CurrentData {
+            speed_knots: pick_u64_max(bv, 176, 8, 251).map(|v| v as f64 / 10.0),
+            direction: pick_u64_max(bv, 184, 9, 359).map(|v| v as u16),
+            depth: pick_u64_max(bv, 193, 5, 30).map(|v| v as u8),
+        },
+        current_3: CurrentData {
+            speed_knots: pick_u64_max(bv, 198, 8, 251).map(|v| v as f64 / 10.0),
+            direction: pick_u64_max(bv, 206, 9, 359).map(|v| v as u16),
+            depth: pick_u64_max(bv, 215, 5, 30).map(|v| v as u8),
+        },
+        wave_height: pick_u64_max(bv, 220, 8, 251).map(|v| v as f64 / 10.0),
+        wave_period: pick_u64_max(bv, 228, 6, 60).map(|v| v as u8),
+        wave_direction: pick_u64_max(bv, 234, 9, 359).map(|v| v as u16),
+        swell_height: pick_u64_max(bv, 243, 8, 251).map(|v| v as f64 / 10.0),
+        swell_period: pick_u64_max(bv, 251, 6, 60).map(|v| v as u8),
+        swell_direction: pick_u64_max(bv, 257, 9, 359).map(|v| v as u16),
+        sea_state: pick_u64_max(bv, 266, 4, 12).map(|v| v as u8),
+        water_temperature: {
+            let raw = pick_i64(bv, 270, 10);
+            if (-100..=500).contains(&raw) {
+                Some(raw as f64 / 10.0)
+            } else {
+                None
+            }
+        },
+        precipitation: PrecipitationType::new(pick_u64(bv, 280, 3) as u8),
+        salinity: pick_u64_max(bv, 283, 9, 500).map(|v| v as f64 / 10.0),
+        ice: match pick_u64(bv, 292, 2) {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        },
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_dac1_fid31() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,1,1,,A,83KFmf@0GheUv1f8m6C7QQ9hfHC=0Mne<R1H65cwe7wvlO3iVAwwnQ1u9100,0*4B",
+        ) {
+            Ok(ps) => match ps {
+                // The expected result
+                ParsedMessage::BinaryBroadcastMessage(bbm) => {
+                    assert_eq!(bbm.mmsi, 230012345);
+                    assert_eq!(bbm.dac, 1);
+                    assert_eq!(bbm.fid, 31);
+                    assert_eq!(bbm.data.len(), 304);
+                    match bbm.application {
+                        Some(BinaryApplication::MeteorologicalHydrographic(mh)) => {
+                            assert::close(mh.longitude.unwrap_or(0.0), 24.9, 0.0001);
+                            assert::close(mh.latitude.unwrap_or(0.0), 60.15, 0.0001);
+                            assert!(mh.high_position_accuracy);
+                            assert_eq!(mh.utc_day, Some(18));
+                            assert_eq!(mh.utc_hour, Some(12));
+                            assert_eq!(mh.utc_minute, Some(30));
+                            assert_eq!(mh.wind_speed_knots, Some(12));
+                            assert_eq!(mh.wind_gust_knots, Some(18));
+                            assert_eq!(mh.wind_direction, Some(225));
+                            assert_eq!(mh.wind_gust_direction, Some(230));
+                            assert::close(mh.air_temperature.unwrap_or(0.0), 15.3, 0.01);
+                            assert_eq!(mh.relative_humidity, Some(80));
+                            assert::close(mh.dew_point.unwrap_or(0.0), 11.9, 0.01);
+                            assert_eq!(mh.air_pressure, Some(1013));
+                            assert_eq!(mh.air_pressure_tendency, Some(Tendency::Increasing));
+                            assert::close(mh.visibility_nm.unwrap_or(0.0), 10.0, 0.01);
+                            assert!(!mh.visibility_greater_than);
+                            assert::close(mh.water_level.unwrap_or(0.0), 0.35, 0.001);
+                            assert_eq!(mh.water_level_trend, Some(Tendency::Steady));
+                            assert::close(mh.surface_current.speed_knots.unwrap_or(0.0), 1.2, 0.01);
+                            assert_eq!(mh.surface_current.direction, Some(90));
+                            assert_eq!(mh.current_2, CurrentData::default());
+                            assert_eq!(mh.current_3, CurrentData::default());
+                            assert::close(mh.wave_height.unwrap_or(0.0), 1.5, 0.01);
+                            assert_eq!(mh.wave_period, Some(6));
+                            assert_eq!(mh.wave_direction, Some(200));
+                            assert_eq!(mh.swell_height, None);
+                            assert_eq!(mh.swell_period, None);
+                            assert_eq!(mh.swell_direction, None);
+                            assert_eq!(mh.sea_state, Some(4));
+                            assert::close(mh.water_temperature.unwrap_or(0.0), 12.5, 0.01);
+                            assert_eq!(mh.precipitation, Some(PrecipitationType::Rain));
+                            assert::close(mh.salinity.unwrap_or(0.0), 6.5, 0.01);
+                            assert_eq!(mh.ice, Some(false));
+                        }
+                        _ => {
+                            assert!(false);
+                        }
+                    }
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+}
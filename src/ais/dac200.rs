@@ -0,0 +1,616 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! DAC 200: Inland AIS application messages (European RIS standard)
+
+use super::*;
+use application::{pick_latitude_10000, pick_longitude_10000, pick_nonzero, pick_u64_max};
+
+// -------------------------------------------------------------------------------------------------
+
+/// DAC 200, FID 10: Inland ship static and voyage related data
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlandStaticVoyageData {
+    /// Unique European vessel identification number, ENI (8 characters)
+    pub eni: Option<String>,
+
+    /// Length of ship in metres (0.1 m resolution)
+    pub length: Option<f64>,
+
+    /// Beam of ship in metres (0.1 m resolution)
+    pub beam: Option<f64>,
+
+    /// Combination or ship type according to ERI classification (14 bits)
+    pub eri_code: u16,
+
+    /// Ship type corresponding to the ERI code if it can be mapped
+    pub ship_type: Option<ShipType>,
+
+    /// Number of blue cones or blue lights, or B-flag
+    pub hazardous_cargo: Option<HazardousCargo>,
+
+    /// Draught in metres (0.01 m resolution)
+    pub draught: Option<f64>,
+
+    /// True if loaded, false if unloaded
+    pub loaded: Option<bool>,
+
+    /// Speed information from a certified sensor (high quality)
+    pub high_speed_quality: bool,
+
+    /// Course information from a certified sensor (high quality)
+    pub high_course_quality: bool,
+
+    /// Heading information from a certified sensor (high quality)
+    pub high_heading_quality: bool,
+}
+
+/// Hazardous cargo indication of inland vessels
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HazardousCargo {
+    NoBlueCones,
+    OneBlueCone,
+    TwoBlueCones,
+    ThreeBlueCones,
+    BFlag,
+}
+
+impl HazardousCargo {
+    fn new(raw: u8) -> Option<HazardousCargo> {
+        match raw {
+            0 => Some(HazardousCargo::NoBlueCones),
+            1 => Some(HazardousCargo::OneBlueCone),
+            2 => Some(HazardousCargo::TwoBlueCones),
+            3 => Some(HazardousCargo::ThreeBlueCones),
+            4 => Some(HazardousCargo::BFlag),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for HazardousCargo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HazardousCargo::NoBlueCones => write!(f, "0 blue cones/lights"),
+            HazardousCargo::OneBlueCone => write!(f, "1 blue cone/light"),
+            HazardousCargo::TwoBlueCones => write!(f, "2 blue cones/lights"),
+            HazardousCargo::ThreeBlueCones => write!(f, "3 blue cones/lights"),
+            HazardousCargo::BFlag => write!(f, "B-flag"),
+        }
+    }
+}
+
+/// Map ERI ship type code to the corresponding AIS ship type. Returns `None` for unknown codes.
+pub fn eri_ship_type(eri_code: u16) -> Option<ShipType> {
+    match eri_code {
+        8000 => Some(ShipType::NotAvailable),
+        8010 | 8030 | 8050 | 8070 | 8090 | 8110 | 8210 | 8220 | 8230 | 8240 | 8250 | 8260
+        | 8270 | 8280 | 8290 | 1500 | 1510 | 1520 => Some(ShipType::Cargo),
+        8020 | 8021 | 8022 | 8023 | 8040 | 8060 | 8080 | 8100 | 8120 | 8310 | 8320 | 8330
+        | 8340 | 8350 | 8360 | 8370 | 8380 | 8390 | 8500 | 1530 | 1540 => Some(ShipType::Tanker),
+        8130 | 8140 | 8410 | 8420 => Some(ShipType::Towing),
+        8150 | 8160 | 8161 | 8162 | 8163 | 8170 | 8180 | 8430 | 8450 | 8470 | 8490 | 8510 => {
+            Some(ShipType::Other)
+        }
+        8400 => Some(ShipType::Tug),
+        8440 | 8441 | 8443 | 8444 => Some(ShipType::Passenger),
+        8442 => Some(ShipType::MedicalTransport),
+        8460 => Some(ShipType::DredgingOrUnderwaterOps),
+        8480 => Some(ShipType::Fishing),
+        1850 => Some(ShipType::PleasureCraft),
+        1900 | 1910 | 1920 => Some(ShipType::HighSpeedCraft),
+        _ => None,
+    }
+}
+
+/// Decode inland ship static and voyage related data (FID 10).
+pub(crate) fn decode_static_voyage_data(bv: &BitVec) -> Option<InlandStaticVoyageData> {
+    if bv.len() < 104 {
+        return None;
+    }
+    let eri_code = pick_u64(bv, 71, 14) as u16;
+    Some(InlandStaticVoyageData {
+        eni: {
+            let raw = pick_string(bv, 0, 8);
+            let eni = raw.trim();
+            if !eni.is_empty() && eni != "00000000" {
+                Some(eni.into())
+            } else {
+                None
+            }
+        },
+        length: pick_nonzero(bv, 48, 13).map(|v| v as f64 / 10.0),
+        beam: pick_nonzero(bv, 61, 10).map(|v| v as f64 / 10.0),
+        eri_code,
+        ship_type: eri_ship_type(eri_code),
+        hazardous_cargo: HazardousCargo::new(pick_u64(bv, 85, 3) as u8),
+        draught: pick_nonzero(bv, 88, 11).map(|v| v as f64 / 100.0),
+        loaded: match pick_u64(bv, 99, 2) {
+            1 => Some(true),
+            2 => Some(false),
+            _ => None,
+        },
+        high_speed_quality: pick_u64(bv, 101, 1) != 0,
+        high_course_quality: pick_u64(bv, 102, 1) != 0,
+        high_heading_quality: pick_u64(bv, 103, 1) != 0,
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// DAC 200, FID 55: Number of persons on board
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlandPersonsOnBoard {
+    /// Number of crew members
+    pub crew: Option<u8>,
+
+    /// Number of passengers
+    pub passengers: Option<u16>,
+
+    /// Number of shipboard personnel
+    pub shipboard_personnel: Option<u8>,
+}
+
+/// Decode number of persons on board (FID 55).
+pub(crate) fn decode_persons_on_board(bv: &BitVec) -> Option<InlandPersonsOnBoard> {
+    if bv.len() < 29 {
+        return None;
+    }
+    Some(InlandPersonsOnBoard {
+        crew: pick_u64_max(bv, 0, 8, 254).map(|v| v as u8),
+        passengers: pick_u64_max(bv, 8, 13, 8190).map(|v| v as u16),
+        shipboard_personnel: pick_u64_max(bv, 21, 8, 254).map(|v| v as u8),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// DAC 200, FID 23: EMMA weather warning
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlandEmmaWarning {
+    /// Start of the validity period
+    pub start_time: Option<DateTime<Utc>>,
+
+    /// End of the validity period
+    pub end_time: Option<DateTime<Utc>>,
+
+    /// Longitude of the start of the fairway section
+    pub start_longitude: Option<f64>,
+
+    /// Latitude of the start of the fairway section
+    pub start_latitude: Option<f64>,
+
+    /// Longitude of the end of the fairway section
+    pub end_longitude: Option<f64>,
+
+    /// Latitude of the end of the fairway section
+    pub end_latitude: Option<f64>,
+
+    /// Type of weather warning
+    pub warning_type: Option<EmmaWarningType>,
+
+    /// Minimum value, unit depends on the warning type
+    pub min_value: Option<i16>,
+
+    /// Maximum value, unit depends on the warning type
+    pub max_value: Option<i16>,
+
+    /// Warning classification: 1 = slight, 2 = medium, 3 = strong/heavy
+    pub classification: Option<u8>,
+
+    /// Wind direction in degrees (resolution of 45 degrees)
+    pub wind_direction: Option<u16>,
+}
+
+impl LatLon for InlandEmmaWarning {
+    fn latitude(&self) -> Option<f64> {
+        self.start_latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.start_longitude
+    }
+}
+
+/// Type of EMMA weather warning
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmmaWarningType {
+    Wind,
+    Rain,
+    SnowAndIce,
+    Thunderstorm,
+    Fog,
+    LowTemperature,
+    HighTemperature,
+    Flood,
+    ForestFire,
+}
+
+impl EmmaWarningType {
+    fn new(raw: u8) -> Option<EmmaWarningType> {
+        match raw {
+            1 => Some(EmmaWarningType::Wind),
+            2 => Some(EmmaWarningType::Rain),
+            3 => Some(EmmaWarningType::SnowAndIce),
+            4 => Some(EmmaWarningType::Thunderstorm),
+            5 => Some(EmmaWarningType::Fog),
+            6 => Some(EmmaWarningType::LowTemperature),
+            7 => Some(EmmaWarningType::HighTemperature),
+            8 => Some(EmmaWarningType::Flood),
+            9 => Some(EmmaWarningType::ForestFire),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for EmmaWarningType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmmaWarningType::Wind => write!(f, "wind"),
+            EmmaWarningType::Rain => write!(f, "rain"),
+            EmmaWarningType::SnowAndIce => write!(f, "snow and ice"),
+            EmmaWarningType::Thunderstorm => write!(f, "thunderstorm"),
+            EmmaWarningType::Fog => write!(f, "fog"),
+            EmmaWarningType::LowTemperature => write!(f, "low temperature"),
+            EmmaWarningType::HighTemperature => write!(f, "high temperature"),
+            EmmaWarningType::Flood => write!(f, "flood"),
+            EmmaWarningType::ForestFire => write!(f, "fire in the forests"),
+        }
+    }
+}
+
+/// Decode EMMA weather warning (FID 23).
+pub(crate) fn decode_emma_warning(bv: &BitVec) -> Option<InlandEmmaWarning> {
+    if bv.len() < 194 {
+        return None;
+    }
+    Some(InlandEmmaWarning {
+        start_time: pick_date_time(bv, 0, 34),
+        end_time: pick_date_time(bv, 17, 45),
+        start_longitude: pick_longitude_10000(bv, 56),
+        start_latitude: pick_latitude_10000(bv, 84),
+        end_longitude: pick_longitude_10000(bv, 111),
+        end_latitude: pick_latitude_10000(bv, 139),
+        warning_type: EmmaWarningType::new(pick_u64(bv, 166, 4) as u8),
+        min_value: pick_value(bv, 170),
+        max_value: pick_value(bv, 179),
+        classification: pick_nonzero(bv, 188, 2).map(|v| v as u8),
+        wind_direction: match pick_u64(bv, 190, 4) {
+            raw @ 1..=8 => Some((raw as u16 - 1) * 45),
+            _ => None,
+        },
+    })
+}
+
+/// Pick date (year since 2000, month and day) and time (hour and minute) given at separate
+/// indices. Returns `None` if any of the fields is not available.
+fn pick_date_time(bv: &BitVec, date_index: usize, time_index: usize) -> Option<DateTime<Utc>> {
+    let year = pick_u64(bv, date_index, 8) as i32;
+    if year == 0 {
+        return None;
+    }
+    parse_valid_utc(
+        2000 + year,
+        pick_u64(bv, date_index + 8, 4) as u32,
+        pick_u64(bv, date_index + 12, 5) as u32,
+        pick_u64(bv, time_index, 5) as u32,
+        pick_u64(bv, time_index + 5, 6) as u32,
+        0,
+        0,
+    )
+    .ok()
+}
+
+/// Pick EMMA minimum or maximum value (sign bit and 8 bits). Value 255 means not available.
+fn pick_value(bv: &BitVec, index: usize) -> Option<i16> {
+    let value = pick_u64_max(bv, index + 1, 8, 254)? as i16;
+    if pick_u64(bv, index, 1) != 0 {
+        Some(-value)
+    } else {
+        Some(value)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// DAC 200, FID 24: Water levels
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlandWaterLevels {
+    /// UN country code (2 characters)
+    pub country: String,
+
+    /// Water levels of up to four gauges
+    pub gauges: Vec<WaterLevelGauge>,
+}
+
+/// Water level reported by a gauge
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WaterLevelGauge {
+    /// National gauge ID (11 bits)
+    pub gauge_id: u16,
+
+    /// Water level in metres (0.01 m resolution)
+    pub water_level: Option<f64>,
+}
+
+/// Decode water levels (FID 24). Gauges with zero ID are left out.
+pub(crate) fn decode_water_levels(bv: &BitVec) -> Option<InlandWaterLevels> {
+    if bv.len() < 12 + 25 {
+        return None;
+    }
+    let count = ((bv.len() - 12) / 25).min(4);
+    Some(InlandWaterLevels {
+        country: pick_string(bv, 0, 2),
+        gauges: (0..count)
+            .filter_map(|i| {
+                let index = 12 + i * 25;
+                let gauge_id = pick_u64(bv, index, 11) as u16;
+                if gauge_id == 0 {
+                    return None;
+                }
+                Some(WaterLevelGauge {
+                    gauge_id,
+                    water_level: pick_nonzero(bv, index + 12, 13).map(|v| {
+                        if pick_u64(bv, index + 11, 1) != 0 {
+                            v as f64 / 100.0
+                        } else {
+                            -(v as f64) / 100.0
+                        }
+                    }),
+                })
+            })
+            .collect(),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// DAC 200, FID 40: Signal station status
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InlandSignalStatus {
+    /// Longitude of the signal
+    pub longitude: Option<f64>,
+
+    /// Latitude of the signal
+    pub latitude: Option<f64>,
+
+    /// Signal form according to the national table (1-14)
+    pub signal_form: Option<u8>,
+
+    /// Orientation of the signal in degrees
+    pub orientation: Option<u16>,
+
+    /// Direction of impact
+    pub impact_direction: Option<ImpactDirection>,
+
+    /// Status of the nine lights of the signal, `None` for unknown status
+    pub lights: Vec<Option<SignalLight>>,
+}
+
+impl LatLon for InlandSignalStatus {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+/// Direction of impact of an inland signal
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImpactDirection {
+    Upstream,
+    Downstream,
+    LeftBank,
+    RightBank,
+}
+
+impl ImpactDirection {
+    fn new(raw: u8) -> Option<ImpactDirection> {
+        match raw {
+            1 => Some(ImpactDirection::Upstream),
+            2 => Some(ImpactDirection::Downstream),
+            3 => Some(ImpactDirection::LeftBank),
+            4 => Some(ImpactDirection::RightBank),
+            _ => None,
+        }
+    }
+}
+
+/// Status of a single light of an inland signal
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignalLight {
+    NoLight,
+    White,
+    Yellow,
+    Green,
+    Red,
+    WhiteFlashing,
+    YellowFlashing,
+}
+
+impl SignalLight {
+    fn new(raw: u8) -> Option<SignalLight> {
+        match raw {
+            1 => Some(SignalLight::NoLight),
+            2 => Some(SignalLight::White),
+            3 => Some(SignalLight::Yellow),
+            4 => Some(SignalLight::Green),
+            5 => Some(SignalLight::Red),
+            6 => Some(SignalLight::WhiteFlashing),
+            7 => Some(SignalLight::YellowFlashing),
+            _ => None,
+        }
+    }
+}
+
+/// Decode signal station status (FID 40). The light status is a nine digit decimal number with
+/// one digit per light.
+pub(crate) fn decode_signal_status(bv: &BitVec) -> Option<InlandSignalStatus> {
+    if bv.len() < 101 {
+        return None;
+    }
+    let light_status = pick_u64(bv, 71, 30);
+    Some(InlandSignalStatus {
+        longitude: pick_longitude_10000(bv, 0),
+        latitude: pick_latitude_10000(bv, 28),
+        signal_form: pick_u64_max(bv, 55, 4, 14)
+            .filter(|v| *v != 0)
+            .map(|v| v as u8),
+        orientation: pick_u64_max(bv, 59, 9, 359).map(|v| v as u16),
+        impact_direction: ImpactDirection::new(pick_u64(bv, 68, 3) as u8),
+        lights: (0..9)
+            .rev()
+            .map(|i| SignalLight::new((light_status / 10u64.pow(i) % 10) as u8))
+            .collect(),
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Parse the given binary message sentence and return its decoded application data.
+    fn parse_application(sentence: &str) -> Option<BinaryApplication> {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(sentence) {
+            Ok(ParsedMessage::BinaryBroadcastMessage(bbm)) => bbm.application,
+            Ok(ParsedMessage::BinaryAddressedMessage(bam)) => bam.application,
+            Ok(_) => None,
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac200_fid10() {
+        match parse_application("!AIVDM,1,1,,A,839Lg00j2d<dtee<LR9Pq?cj8hl0,0*2F") {
+            Some(BinaryApplication::InlandStaticVoyageData(svd)) => {
+                assert_eq!(svd.eni, Some("02326412".into()));
+                assert_eq!(svd.length, Some(110.0));
+                assert_eq!(svd.beam, Some(11.4));
+                assert_eq!(svd.eri_code, 8030);
+                assert_eq!(svd.ship_type, Some(ShipType::Cargo));
+                assert_eq!(svd.hazardous_cargo, Some(HazardousCargo::TwoBlueCones));
+                assert_eq!(svd.draught, Some(2.8));
+                assert_eq!(svd.loaded, Some(true));
+                assert!(svd.high_speed_quality);
+                assert!(!svd.high_course_quality);
+                assert!(svd.high_heading_quality);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac200_fid23() {
+        match parse_application("!AIVDM,1,1,,A,839Lg00j5iEARbT<19p3OHp3a0701o1<1lfe04:2nH0,2*19") {
+            Some(BinaryApplication::InlandEmmaWarning(ew)) => {
+                assert_eq!(ew.start_time, Some(Utc.ymd(2021, 5, 3).and_hms(6, 0, 0)));
+                assert_eq!(ew.end_time, Some(Utc.ymd(2021, 5, 4).and_hms(18, 30, 0)));
+                assert::close(ew.start_longitude.unwrap_or(0.0), 6.1, 0.000001);
+                assert::close(ew.start_latitude.unwrap_or(0.0), 50.9, 0.000001);
+                assert::close(ew.end_longitude.unwrap_or(0.0), 6.5, 0.000001);
+                assert::close(ew.end_latitude.unwrap_or(0.0), 51.0, 0.000001);
+                assert_eq!(ew.warning_type, Some(EmmaWarningType::Wind));
+                assert_eq!(ew.min_value, Some(20));
+                assert_eq!(ew.max_value, Some(45));
+                assert_eq!(ew.classification, Some(2));
+                assert_eq!(ew.wind_direction, Some(225));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac200_fid24() {
+        match parse_application("!AIVDM,1,1,,A,839Lg00j611@jhkPIP2<00000000,0*0D") {
+            Some(BinaryApplication::InlandWaterLevels(wl)) => {
+                assert_eq!(wl.country, "DE");
+                assert_eq!(wl.gauges.len(), 2);
+                assert_eq!(wl.gauges[0].gauge_id, 101);
+                assert_eq!(wl.gauges[0].water_level, Some(4.12));
+                assert_eq!(wl.gauges[1].gauge_id, 102);
+                assert_eq!(wl.gauges[1].water_level, Some(-0.35));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac200_fid40() {
+        match parse_application("!AIVDM,1,1,,A,839Lg00j:0?r4@>TgD1Ua@85VSP0,0*02") {
+            Some(BinaryApplication::InlandSignalStatus(ss)) => {
+                assert::close(ss.longitude.unwrap_or(0.0), 6.95, 0.000001);
+                assert::close(ss.latitude.unwrap_or(0.0), 50.94, 0.000001);
+                assert_eq!(ss.signal_form, Some(3));
+                assert_eq!(ss.orientation, Some(90));
+                assert_eq!(ss.impact_direction, Some(ImpactDirection::Downstream));
+                assert_eq!(ss.lights.len(), 9);
+                assert_eq!(ss.lights[0], Some(SignalLight::Red));
+                assert_eq!(ss.lights[1], Some(SignalLight::Green));
+                assert_eq!(ss.lights[2], Some(SignalLight::NoLight));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dac200_fid55() {
+        match parse_application("!AIVDM,1,1,,A,639Lg04rEGS0<SL50tOp00000000,0*4B") {
+            Some(BinaryApplication::InlandPersonsOnBoard(pob)) => {
+                assert_eq!(pob.crew, Some(5));
+                assert_eq!(pob.passengers, Some(120));
+                assert_eq!(pob.shipboard_personnel, None);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eri_ship_type() {
+        assert_eq!(eri_ship_type(8010), Some(ShipType::Cargo));
+        assert_eq!(eri_ship_type(8021), Some(ShipType::Tanker));
+        assert_eq!(eri_ship_type(8400), Some(ShipType::Tug));
+        assert_eq!(eri_ship_type(8441), Some(ShipType::Passenger));
+        assert_eq!(eri_ship_type(1234), None);
+    }
+}
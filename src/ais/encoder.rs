@@ -0,0 +1,326 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! AIS VDM/VDO sentence encoder
+
+use super::*;
+
+/// Default maximum number of payload characters in a single sentence. This keeps the sentences
+/// within the 82 character limit of NMEA 0183.
+const DEFAULT_MAX_PAYLOAD_LENGTH: usize = 60;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Encoder which builds AIS VDM/VDO sentences from the data structures produced by `NmeaParser`.
+/// Payloads too long for a single sentence are split into several sentences which share a
+/// sequential message ID. The encoder keeps track of the ID between calls.
+///
+/// Talker ID is chosen based on the station of the message and `!AIVDO` is used for own vessel
+/// data. Vessel dynamic data is encoded as type 1 for Class A and type 18 for Class B. Vessel
+/// static data is encoded as type 24 parts A and B if it contains any Class B specific fields,
+/// and as type 5 otherwise.
+///
+/// `VesselDynamicData` does not record whether a Class A position report was received as type 1,
+/// 2 or 3, so reports parsed from types 2 and 3 are re-encoded as type 1. The radio status field
+/// is copied as is, which means an ITDMA state of a type 3 report is sent with the SOTDMA
+/// message type.
+pub struct VdmEncoder {
+    radio_channel: String,
+    max_payload_length: usize,
+    next_message_id: u8,
+}
+
+impl VdmEncoder {
+    /// Construct an encoder which uses radio channel A.
+    pub fn new() -> VdmEncoder {
+        VdmEncoder {
+            radio_channel: "A".into(),
+            max_payload_length: DEFAULT_MAX_PAYLOAD_LENGTH,
+            next_message_id: 0,
+        }
+    }
+
+    /// Set the radio channel code of the sentences. Empty string leaves the field empty.
+    pub fn set_radio_channel(&mut self, radio_channel: &str) {
+        self.radio_channel = radio_channel.into();
+    }
+
+    /// Set the maximum number of payload characters in a single sentence.
+    pub fn set_max_payload_length(&mut self, max_payload_length: usize) {
+        self.max_payload_length = max_payload_length.max(1);
+    }
+
+    /// Encode the given message into one or more complete sentences including checksums.
+    pub fn encode(&mut self, message: &ParsedMessage) -> Result<Vec<String>, ParseError> {
+        match message {
+            ParsedMessage::VesselDynamicData(vdd) => {
+                let bv = match vdd.ais_type {
+                    AisClass::ClassB => vdm_t18::encode(vdd),
+                    _ => vdm_t1t2t3::encode(vdd),
+                };
+                self.encode_payload(&bv, vdd.station, vdd.own_vessel)
+            }
+            ParsedMessage::VesselStaticData(vsd) => {
+                if vsd.equipment_vendor_id.is_some()
+                    || vsd.equipment_model.is_some()
+                    || vsd.equipment_serial_number.is_some()
+                    || vsd.mothership_mmsi.is_some()
+                {
+                    let (part_a, part_b) = vdm_t24::encode(vsd);
+                    let mut sentences =
+                        self.encode_payload(&part_a, Station::MobileStation, vsd.own_vessel)?;
+                    sentences.append(&mut self.encode_payload(
+                        &part_b,
+                        Station::MobileStation,
+                        vsd.own_vessel,
+                    )?);
+                    Ok(sentences)
+                } else {
+                    let bv = vdm_t5::encode(vsd);
+                    self.encode_payload(&bv, Station::MobileStation, vsd.own_vessel)
+                }
+            }
+            ParsedMessage::BaseStationReport(bsr) => {
+                self.encode_payload(&vdm_t4::encode(bsr, 4), bsr.station, bsr.own_vessel)
+            }
+            ParsedMessage::StandardSarAircraftPositionReport(sar) => {
+                self.encode_payload(&vdm_t9::encode(sar), sar.station, sar.own_vessel)
+            }
+            ParsedMessage::UtcDateResponse(bsr) => {
+                self.encode_payload(&vdm_t4::encode(bsr, 11), bsr.station, bsr.own_vessel)
+            }
+            ParsedMessage::SafetyRelatedBroadcastMessage(srbm) => {
+                self.encode_payload(&vdm_t14::encode(srbm), srbm.station, srbm.own_vessel)
+            }
+            ParsedMessage::ExtendedClassBPositionReport(ecbpr) => self.encode_payload(
+                &vdm_t19::encode(ecbpr),
+                ecbpr.dynamic_data.station,
+                ecbpr.dynamic_data.own_vessel,
+            ),
+            ParsedMessage::AidToNavigationReport(atnr) => {
+                self.encode_payload(&vdm_t21::encode(atnr), atnr.station, atnr.own_vessel)
+            }
+            _ => Err(ParseError::UnsupportedSentenceType(
+                "Message can't be encoded as VDM/VDO".into(),
+            )),
+        }
+    }
+
+    /// Encode the given raw message bits into one or more complete sentences including
+    /// checksums.
+    pub fn encode_payload(
+        &mut self,
+        bv: &BitVec,
+        station: Station,
+        own_vessel: bool,
+    ) -> Result<Vec<String>, ParseError> {
+        let (payload, fill_bits) = armor_payload(bv);
+        let chars: Vec<char> = payload.chars().collect();
+        let fragments: Vec<String> = chars
+            .chunks(self.max_payload_length)
+            .map(|c| c.iter().collect())
+            .collect();
+        let fragment_count = fragments.len().max(1);
+        if fragment_count > MAX_FRAGMENT_COUNT as usize {
+            return Err(ParseError::InvalidSentence(format!(
+                "Payload requires too many fragments: {}",
+                fragment_count
+            )));
+        }
+
+        // Sequential message ID is only needed for multi-sentence messages
+        let message_id = if fragment_count > 1 {
+            let id = self.next_message_id;
+            self.next_message_id = (self.next_message_id + 1) % 10;
+            id.to_string()
+        } else {
+            "".into()
+        };
+
+        let sentence_type = format!(
+            "!{}{}",
+            talker_id(station),
+            if own_vessel { "VDO" } else { "VDM" }
+        );
+        let mut sentences = Vec::with_capacity(fragment_count);
+        for i in 0..fragment_count {
+            let sentence = format!(
+                "{},{},{},{},{},{},{}",
+                sentence_type,
+                fragment_count,
+                i + 1,
+                message_id,
+                self.radio_channel,
+                fragments.get(i).map(|s| s.as_str()).unwrap_or(""),
+                if i + 1 == fragment_count {
+                    fill_bits
+                } else {
+                    0
+                }
+            );
+            sentences.push(append_checksum(&sentence));
+        }
+        Ok(sentences)
+    }
+}
+
+impl Default for VdmEncoder {
+    fn default() -> Self {
+        VdmEncoder::new()
+    }
+}
+
+/// Return the talker ID of the given AIS station.
+fn talker_id(station: Station) -> &'static str {
+    match station {
+        Station::BaseStation => "AB",
+        Station::DependentAisBaseStation => "AD",
+        Station::MobileStation => "AI",
+        Station::AidToNavigationStation => "AN",
+        Station::AisReceivingStation => "AR",
+        Station::LimitedBaseStation => "AS",
+        Station::AisTransmittingStation => "AT",
+        Station::RepeaterStation => "AX",
+        Station::Other => "BS",
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Parse the given sentences, encode the resulting message and check that parsing the encoded
+    /// sentences gives the same message. Encoded sentences are returned.
+    fn round_trip(sentences: &[&str]) -> Vec<String> {
+        let mut p = NmeaParser::new();
+        let mut original = ParsedMessage::Incomplete;
+        for s in sentences {
+            match p.parse_sentence(s) {
+                Ok(ps) => original = ps,
+                Err(e) => assert_eq!(e.to_string(), "OK"),
+            }
+        }
+        assert_ne!(original, ParsedMessage::Incomplete);
+
+        let encoded = VdmEncoder::new().encode(&original).unwrap();
+        let mut p = NmeaParser::new();
+        let mut decoded = ParsedMessage::Incomplete;
+        for s in &encoded {
+            match p.parse_sentence(s) {
+                Ok(ps) => decoded = ps,
+                Err(e) => assert_eq!(e.to_string(), "OK"),
+            }
+        }
+        assert_eq!(decoded, original);
+        encoded
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        // Types 1-3
+        assert_eq!(
+            round_trip(&["!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"]),
+            vec!["!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A"]
+        );
+        round_trip(&["!AIVDM,1,1,,A,16SteH0P00Jt63hHaa6SagvJ087r,0*42"]);
+        round_trip(&["!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40"]);
+
+        // Type 4
+        assert_eq!(
+            round_trip(&["!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D"]),
+            vec!["!AIVDM,1,1,,A,403OviQuMGCqWrRO9>E6fE700@GO,0*4D"]
+        );
+
+        // Type 5
+        round_trip(&[
+            "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+            "!AIVDM,2,2,1,A,88888888880,2*25",
+        ]);
+
+        // Type 9
+        round_trip(&["!AIVDM,1,1,,B,91b55wi;hbOS@OdQAC062Ch2089h,0*30"]);
+
+        // Type 11
+        round_trip(&["!AIVDM,1,1,,B,;4R33:1uUK2F`q?mOt@@GoQ00000,0*5D"]);
+
+        // Type 14
+        assert_eq!(
+            round_trip(&["!AIVDM,1,1,,A,>5?Per18=HB1U:1@E=B0m<L,2*51"]),
+            vec!["!AIVDM,1,1,,A,>5?Per18=HB1U:1@E=B0m<L,2*51"]
+        );
+
+        // Type 18
+        round_trip(&["!AIVDM,1,1,,A,B52K>;h00Fc>jpUlNV@ikwpUoP06,0*4C"]);
+
+        // Type 19
+        round_trip(&["!AIVDM,1,1,,B,C5N3SRgPEnJGEBT>NhWAwwo862PaLELTBJ:V00000000S0D:R220,0*0B"]);
+
+        // Type 21
+        round_trip(&[
+            "!AIVDM,2,1,5,B,E1mg=5J1T4W0h97aRh6ba84<h2d;W:Te=eLvH50```q,0*46",
+            "!AIVDM,2,2,5,B,:D44QDlp0C1DU00,2*36",
+        ]);
+
+        // Type 24
+        round_trip(&[
+            "!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D",
+            "!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40",
+        ]);
+    }
+
+    #[test]
+    fn test_encode_fragments() {
+        let mut encoder = VdmEncoder::new();
+        encoder.set_radio_channel("B");
+        let msg = ParsedMessage::SafetyRelatedBroadcastMessage(SafetyRelatedBroadcastMessage {
+            own_vessel: true,
+            station: Station::BaseStation,
+            mmsi: 2300049,
+            text: "THIS IS A LONG SAFETY RELATED MESSAGE WHICH DOES NOT FIT IN A SINGLE \
+                   SENTENCE AND HAS TO BE SPLIT INTO SEVERAL FRAGMENTS"
+                .into(),
+        });
+
+        let sentences = encoder.encode(&msg).unwrap();
+        assert_eq!(sentences.len(), 3);
+        assert!(sentences[0].starts_with("!ABVDO,3,1,0,B,"));
+        assert!(sentences[1].starts_with("!ABVDO,3,2,0,B,"));
+        assert!(sentences[2].starts_with("!ABVDO,3,3,0,B,"));
+        assert!(sentences.iter().all(|s| s.len() <= 82));
+
+        // Sequential message ID increases
+        let sentences = encoder.encode(&msg).unwrap();
+        assert!(sentences[0].starts_with("!ABVDO,3,1,1,B,"));
+
+        let mut p = NmeaParser::new();
+        assert_eq!(
+            p.parse_sentence(&sentences[2]),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(
+            p.parse_sentence(&sentences[0]),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(p.parse_sentence(&sentences[1]), Ok(msg));
+
+        // Unsupported message
+        assert!(encoder
+            .encode(&ParsedMessage::UtcDateInquiry(UtcDateInquiry::default()))
+            .is_err());
+    }
+}
@@ -16,10 +16,18 @@ limitations under the License.
 
 //! AIS VDM/VDO data structures
 
+pub(crate) mod application;
+pub(crate) mod dac1_addressed;
+pub(crate) mod dac1_fid22;
+pub(crate) mod dac1_fid31;
+pub(crate) mod dac200;
+mod encoder;
 pub(crate) mod vdm_t1t2t3;
 pub(crate) mod vdm_t4;
 pub(crate) mod vdm_t5;
 pub(crate) mod vdm_t6;
+pub(crate) mod vdm_t7;
+pub(crate) mod vdm_t8;
 pub(crate) mod vdm_t9;
 pub(crate) mod vdm_t10;
 pub(crate) mod vdm_t11;
@@ -41,8 +49,24 @@ pub(crate) mod vdm_t26;
 pub(crate) mod vdm_t27;
 
 use super::*;
+pub use application::{BinaryApplication, BinaryApplicationDecoder, CustomApplication};
+pub use dac1_addressed::{
+    BerthingData, ClearanceTimeToEnterPort, DangerousCargo, DangerousCargoIndication,
+    ExtendedShipStaticData, LegacyDangerousCargoIndication, MooringPosition, PersonsOnBoard,
+    QuantityUnit, RouteInformation, RouteType, TextDescription, TidalWindow, TidalWindowPeriod,
+};
+pub use dac1_fid22::{AreaNotice, AreaNoticeSubArea, AreaNoticeType, AreaPoint};
+pub use dac1_fid31::{CurrentData, MeteorologicalHydrographicData, PrecipitationType, Tendency};
+pub use dac200::{
+    eri_ship_type, EmmaWarningType, HazardousCargo, ImpactDirection, InlandEmmaWarning,
+    InlandPersonsOnBoard, InlandSignalStatus, InlandStaticVoyageData, InlandWaterLevels,
+    SignalLight, WaterLevelGauge,
+};
+pub use encoder::VdmEncoder;
 pub use vdm_t4::BaseStationReport;
 pub use vdm_t6::BinaryAddressedMessage;
+pub use vdm_t7::BinaryAcknowledge;
+pub use vdm_t8::BinaryBroadcastMessage;
 pub use vdm_t9::StandardSarAircraftPositionReport;
 pub use vdm_t10::UtcDateInquiry;
 pub use vdm_t12::AddressedSafetyRelatedMessage;
@@ -51,6 +75,7 @@ pub use vdm_t14::SafetyRelatedBroadcastMessage;
 pub use vdm_t15::{Interrogation, InterrogationCase};
 pub use vdm_t16::AssignmentModeCommand;
 pub use vdm_t17::DgnssBroadcastBinaryMessage;
+pub use vdm_t19::ExtendedClassBPositionReport;
 pub use vdm_t20::{DataLinkManagementMessage};
 pub use vdm_t21::{AidToNavigationReport, NavAidType};
 pub use vdm_t22::{ChannelManagement};
@@ -62,6 +87,7 @@ pub use vdm_t26::{MultipleSlotBinaryMessage};
 
 /// AIS station based on talker id
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Station {
     BaseStation,             // !AB
     DependentAisBaseStation, // !AD
@@ -100,6 +126,7 @@ impl std::fmt::Display for Station {
 
 /// Types 1, 2, 3 and 18: Position Report Class A, and Long Range AIS Broadcast message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VesselDynamicData {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -203,6 +230,7 @@ pub struct VesselDynamicData {
 
 /// AIS class which is either Class A or Class B
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AisClass {
     /// AIS class not known.
     Unknown,
@@ -242,6 +270,7 @@ impl LatLon for VesselDynamicData {
 
 /// Navigation status for VesselDynamicData
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NavigationStatus {
     UnderWayUsingEngine = 0,        // 0
     AtAnchor = 1,                   // 1
@@ -323,6 +352,7 @@ impl Default for NavigationStatus {
 
 /// Location metadata about positioning system
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PositioningSystemMeta {
     Operative, // When timestamp second is 0-59
     ManualInputMode,
@@ -345,6 +375,7 @@ impl std::fmt::Display for PositioningSystemMeta {
 
 /// Vessel rotation direction
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RotDirection {
     /// Turning port (left, when seen by an observer aboard the vessel looking forward)
     Port,
@@ -376,6 +407,7 @@ impl std::fmt::Display for RotDirection {
 
 /// Types 5 and 24: Ship static voyage related data, and boat static data report.
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VesselStaticData {
     /// True if the data is about own vessel, false if about other vessel.
     pub own_vessel: bool,
@@ -442,6 +474,7 @@ pub struct VesselStaticData {
 
 /// Ship type derived from combined ship and cargo type field
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShipType {
     NotAvailable = 0,             // 0
     Reserved1 = 10,               // 1x
@@ -566,6 +599,7 @@ impl std::fmt::Display for ShipType {
 
 /// Cargo type derived from combined ship and cargo type field
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CargoType {
     Undefined = 10,          // x0
     HazardousCategoryA = 11, // x1
@@ -632,6 +666,7 @@ impl Default for CargoType {
 
 /// EPFD position fix types
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PositionFixType {
     Undefined = 0,                  // 0
     GPS = 1,                        // 1
@@ -989,3 +1024,46 @@ impl VesselStaticData {
         }
     }
 }
+
+// -------------------------------------------------------------------------------------------------
+
+/// Combine ship type and cargo type into the raw ship and cargo type field value.
+pub(crate) fn ship_and_cargo_type(ship_type: ShipType, cargo_type: CargoType) -> u8 {
+    let ship = ship_type.to_value();
+    match ship {
+        10 | 20 | 40 | 60 | 70 | 80 | 90 => ship + cargo_type.to_value() - 10,
+        _ => ship,
+    }
+}
+
+/// Push speed over ground in 1/10 knots (10 bits) to the end of `BitVec`.
+pub(crate) fn push_sog(bv: &mut BitVec, sog_knots: Option<f64>) {
+    match sog_knots {
+        Some(sog) => push_u64(bv, ((sog * 10.0).round() as u64).min(1022), 10),
+        None => push_u64(bv, 1023, 10),
+    }
+}
+
+/// Push course over ground in 1/10 degrees (12 bits) to the end of `BitVec`.
+pub(crate) fn push_cog(bv: &mut BitVec, cog: Option<f64>) {
+    match cog {
+        Some(cog) => push_u64(bv, (cog * 10.0).round() as u64 % 3600, 12),
+        None => push_u64(bv, 0xE10, 12),
+    }
+}
+
+/// Push true heading in degrees (9 bits) to the end of `BitVec`.
+pub(crate) fn push_heading(bv: &mut BitVec, heading_true: Option<f64>) {
+    match heading_true {
+        Some(hdg) => push_u64(bv, hdg.round() as u64 % 360, 9),
+        None => push_u64(bv, 511, 9),
+    }
+}
+
+/// Push the dimensions of the vessel (30 bits) to the end of `BitVec`.
+pub(crate) fn push_dimensions(bv: &mut BitVec, vsd: &VesselStaticData) {
+    push_u64(bv, vsd.dimension_to_bow.unwrap_or(0) as u64, 9);
+    push_u64(bv, vsd.dimension_to_stern.unwrap_or(0) as u64, 9);
+    push_u64(bv, vsd.dimension_to_port.unwrap_or(0) as u64, 6);
+    push_u64(bv, vsd.dimension_to_starboard.unwrap_or(0) as u64, 6);
+}
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 12: Addressed Safety-Related Message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AddressedSafetyRelatedMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
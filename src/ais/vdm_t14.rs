@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 14: Safety-Related Broadcast Message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SafetyRelatedBroadcastMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -52,6 +53,18 @@ pub(crate) fn handle(
     ))
 }
 
+/// Encode `SafetyRelatedBroadcastMessage` as AIS VDM/VDO type 14
+pub(crate) fn encode(srbm: &SafetyRelatedBroadcastMessage) -> BitVec {
+    let char_count = srbm.text.chars().count().min(161);
+    let mut bv = BitVec::with_capacity(40 + char_count * 6);
+    push_u64(&mut bv, 14, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, srbm.mmsi as u64, 30);
+    push_u64(&mut bv, 0, 2);
+    push_string(&mut bv, &srbm.text, char_count);
+    bv
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 16: Assignment Mode Command
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssignmentModeCommand {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
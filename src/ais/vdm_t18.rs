@@ -68,14 +68,14 @@ pub(crate) fn handle(
             }
         },
         timestamp_seconds: pick_u64(&bv, 133, 6) as u8,
-        class_b_unit_flag: { None },
-        class_b_display: Some(pick_u64(&bv, 141, 1) != 0),
-        class_b_dsc: Some(pick_u64(&bv, 142, 1) != 0),
-        class_b_band_flag: Some(pick_u64(&bv, 143, 1) != 0),
-        class_b_msg22_flag: Some(pick_u64(&bv, 144, 1) != 0),
-        class_b_mode_flag: Some(pick_u64(&bv, 145, 1) != 0),
-        raim_flag: pick_u64(&bv, 141, 1) != 0,
-        class_b_css_flag: { None },
+        class_b_unit_flag: Some(pick_u64(&bv, 141, 1) != 0),
+        class_b_display: Some(pick_u64(&bv, 142, 1) != 0),
+        class_b_dsc: Some(pick_u64(&bv, 143, 1) != 0),
+        class_b_band_flag: Some(pick_u64(&bv, 144, 1) != 0),
+        class_b_msg22_flag: Some(pick_u64(&bv, 145, 1) != 0),
+        class_b_mode_flag: Some(pick_u64(&bv, 146, 1) != 0),
+        raim_flag: pick_u64(&bv, 147, 1) != 0,
+        class_b_css_flag: Some(pick_u64(&bv, 148, 1) != 0),
         radio_status: Some(pick_u64(&bv, 149, 19) as u32),
         nav_status: NavigationStatus::NotDefined,
         rot: None,
@@ -86,6 +86,33 @@ pub(crate) fn handle(
     }))
 }
 
+/// Encode `VesselDynamicData` as AIS VDM/VDO type 18: Standard Class B CS Position Report
+pub(crate) fn encode(vdd: &VesselDynamicData) -> BitVec {
+    let mut bv = BitVec::with_capacity(168);
+    push_u64(&mut bv, 18, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, vdd.mmsi as u64, 30);
+    push_u64(&mut bv, 0, 8);
+    push_sog(&mut bv, vdd.sog_knots);
+    push_u64(&mut bv, vdd.high_position_accuracy as u64, 1);
+    push_longitude(&mut bv, vdd.longitude);
+    push_latitude(&mut bv, vdd.latitude);
+    push_cog(&mut bv, vdd.cog);
+    push_heading(&mut bv, vdd.heading_true);
+    push_u64(&mut bv, vdd.timestamp_seconds as u64, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, vdd.class_b_unit_flag.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.class_b_display.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.class_b_dsc.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.class_b_band_flag.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.class_b_msg22_flag.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.class_b_mode_flag.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.raim_flag as u64, 1);
+    push_u64(&mut bv, vdd.class_b_css_flag.unwrap_or(false) as u64, 1);
+    push_u64(&mut bv, vdd.radio_status.unwrap_or(0) as u64, 19);
+    bv
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -112,6 +139,13 @@ mod test {
                         assert_eq!(vdd.positioning_system_meta, None);
                         assert_eq!(vdd.special_manoeuvre, None);
                         assert_eq!(vdd.raim_flag, true);
+                        assert_eq!(vdd.class_b_unit_flag, Some(true));
+                        assert_eq!(vdd.class_b_display, Some(false));
+                        assert_eq!(vdd.class_b_dsc, Some(true));
+                        assert_eq!(vdd.class_b_band_flag, Some(true));
+                        assert_eq!(vdd.class_b_msg22_flag, Some(true));
+                        assert_eq!(vdd.class_b_mode_flag, Some(false));
+                        assert_eq!(vdd.class_b_css_flag, Some(true));
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -130,6 +130,53 @@ pub(crate) fn handle(
     }))
 }
 
+/// Encode `VesselDynamicData` as AIS VDM/VDO type 1: Position Report with SOTDMA
+pub(crate) fn encode(vdd: &VesselDynamicData) -> BitVec {
+    let mut bv = BitVec::with_capacity(168);
+    push_u64(&mut bv, 1, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, vdd.mmsi as u64, 30);
+    push_u64(&mut bv, vdd.nav_status.to_value() as u64, 4);
+    push_i64(&mut bv, encode_rot(vdd.rot, vdd.rot_direction), 8);
+    push_sog(&mut bv, vdd.sog_knots);
+    push_u64(&mut bv, vdd.high_position_accuracy as u64, 1);
+    push_longitude(&mut bv, vdd.longitude);
+    push_latitude(&mut bv, vdd.latitude);
+    push_cog(&mut bv, vdd.cog);
+    push_heading(&mut bv, vdd.heading_true);
+    push_u64(&mut bv, vdd.timestamp_seconds as u64, 6);
+    push_u64(
+        &mut bv,
+        match vdd.special_manoeuvre {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        },
+        2,
+    );
+    push_u64(&mut bv, 0, 3);
+    push_u64(&mut bv, vdd.raim_flag as u64, 1);
+    push_u64(&mut bv, vdd.radio_status.unwrap_or(0) as u64, 19);
+    bv
+}
+
+/// Convert rate of turn and its direction to the raw ROT field value.
+fn encode_rot(rot: Option<f64>, rot_direction: Option<RotDirection>) -> i64 {
+    match (rot, rot_direction) {
+        (Some(rot), _) => {
+            let raw = (rot.abs().sqrt() * 4.733 * 126.0 / 708.0).round() as i64;
+            if rot < 0.0 {
+                -raw.min(126)
+            } else {
+                raw.min(126)
+            }
+        }
+        (None, Some(RotDirection::Port)) => -127,
+        (None, Some(RotDirection::Starboard)) => 127,
+        _ => -128,
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 21: Aid-to-Navigation Report
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AidToNavigationReport {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -93,6 +94,7 @@ impl LatLon for AidToNavigationReport {
 
 /// Type of navigation aid
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NavAidType {
     /// Default, type not specified
     NotSpecified, // 0
@@ -229,6 +231,10 @@ impl NavAidType {
             _ => Err(format!("Unrecognized Nav aid type code: {}", raw).into()),
         }
     }
+
+    pub fn to_value(&self) -> u8 {
+        *self as u8
+    }
 }
 
 impl Default for NavAidType {
@@ -326,7 +332,7 @@ pub(crate) fn handle(
             dimension_to_starboard: { Some(pick_u64(&bv, 243, 6) as u16) },
             position_fix_type: { Some(PositionFixType::new(pick_u64(&bv, 249, 4) as u8)) },
             timestamp_seconds: { pick_u64(&bv, 253, 6) as u8 },
-            off_position_indicator: { pick_u64(&bv, 243, 1) != 0 },
+            off_position_indicator: { pick_u64(&bv, 259, 1) != 0 },
             regional: { pick_u64(&bv, 260, 8) as u8 },
             raim_flag: { pick_u64(&bv, 268, 1) != 0 },
             virtual_aid_flag: { pick_u64(&bv, 269, 1) != 0 },
@@ -335,6 +341,40 @@ pub(crate) fn handle(
     ))
 }
 
+/// Encode `AidToNavigationReport` as AIS VDM/VDO type 21. Characters of the name exceeding 20
+/// are stored in the name extension field.
+pub(crate) fn encode(atnr: &AidToNavigationReport) -> BitVec {
+    let extension_len = atnr.name.chars().count().saturating_sub(20).min(14);
+    let mut bv = BitVec::with_capacity(272 + extension_len * 6);
+    push_u64(&mut bv, 21, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, atnr.mmsi as u64, 30);
+    push_u64(&mut bv, atnr.aid_type.to_value() as u64, 5);
+    push_string(&mut bv, &atnr.name, 20);
+    push_u64(&mut bv, atnr.high_position_accuracy as u64, 1);
+    push_longitude(&mut bv, atnr.longitude);
+    push_latitude(&mut bv, atnr.latitude);
+    push_u64(&mut bv, atnr.dimension_to_bow.unwrap_or(0) as u64, 9);
+    push_u64(&mut bv, atnr.dimension_to_stern.unwrap_or(0) as u64, 9);
+    push_u64(&mut bv, atnr.dimension_to_port.unwrap_or(0) as u64, 6);
+    push_u64(&mut bv, atnr.dimension_to_starboard.unwrap_or(0) as u64, 6);
+    push_u64(
+        &mut bv,
+        atnr.position_fix_type.map(|t| t.to_value()).unwrap_or(0) as u64,
+        4,
+    );
+    push_u64(&mut bv, atnr.timestamp_seconds as u64, 6);
+    push_u64(&mut bv, atnr.off_position_indicator as u64, 1);
+    push_u64(&mut bv, atnr.regional as u64, 8);
+    push_u64(&mut bv, atnr.raim_flag as u64, 1);
+    push_u64(&mut bv, atnr.virtual_aid_flag as u64, 1);
+    push_u64(&mut bv, atnr.assigned_mode_flag as u64, 1);
+    push_u64(&mut bv, 0, 1);
+    let extension: String = atnr.name.chars().skip(20).collect();
+    push_string(&mut bv, &extension, extension_len);
+    bv
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -394,4 +434,44 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type21_off_position() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,2,1,7,B,E4eHJhPR37q0000000000000000KUOSc=rq4h00000a,0*4A") {
+            Ok(ps) => match ps {
+                ParsedMessage::Incomplete => {
+                    assert!(true);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+        match p.parse_sentence("!AIVDM,2,2,7,B,@20,4*54") {
+            Ok(ps) => match ps {
+                ParsedMessage::AidToNavigationReport(atnr) => {
+                    assert_eq!(atnr.mmsi, 316021442);
+                    assert_eq!(atnr.aid_type, NavAidType::ReferencePoint);
+                    assert_eq!(atnr.name, "DFO2");
+                    assert::close(atnr.latitude.unwrap_or(0.0), 48.65457, 0.00001);
+                    assert::close(atnr.longitude.unwrap_or(0.0), -123.429155, 0.00001);
+                    assert_eq!(atnr.timestamp_seconds, 18);
+                    assert_eq!(atnr.off_position_indicator, true);
+                    assert_eq!(atnr.raim_flag, true);
+                    assert_eq!(atnr.virtual_aid_flag, false);
+                    assert_eq!(atnr.assigned_mode_flag, false);
+                }
+                _ => {
+                    assert!(false);
+                }
+            },
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
 }
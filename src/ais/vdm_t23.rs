@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 23: Group Assignment Command
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupAssignmentCommand {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -69,6 +70,7 @@ pub struct GroupAssignmentCommand {
 
 /// Station Type (for message type 23).
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StationType {
     /// All types of mobiles (default)
     AllTypes,
@@ -151,6 +153,7 @@ impl StationType {
 
 /// Station interval (for message type 23)
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StationInterval {
     /// As given by the autonomous mode
     Autonomous,
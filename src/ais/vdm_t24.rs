@@ -150,6 +150,48 @@ pub(crate) fn handle(
     }
 }
 
+/// Encode `VesselStaticData` as AIS VDM/VDO type 24 part A and part B.
+pub(crate) fn encode(vsd: &VesselStaticData) -> (BitVec, BitVec) {
+    let mut part_a = BitVec::with_capacity(160);
+    push_u64(&mut part_a, 24, 6);
+    push_u64(&mut part_a, 0, 2);
+    push_u64(&mut part_a, vsd.mmsi as u64, 30);
+    push_u64(&mut part_a, 0, 2);
+    push_string(&mut part_a, vsd.name.as_deref().unwrap_or(""), 20);
+
+    let mut part_b = BitVec::with_capacity(168);
+    push_u64(&mut part_b, 24, 6);
+    push_u64(&mut part_b, 0, 2);
+    push_u64(&mut part_b, vsd.mmsi as u64, 30);
+    push_u64(&mut part_b, 1, 2);
+    push_u64(
+        &mut part_b,
+        ship_and_cargo_type(vsd.ship_type, vsd.cargo_type) as u64,
+        8,
+    );
+    push_string(
+        &mut part_b,
+        vsd.equipment_vendor_id.as_deref().unwrap_or(""),
+        3,
+    );
+    push_u64(&mut part_b, vsd.equipment_model.unwrap_or(0) as u64, 4);
+    push_u64(
+        &mut part_b,
+        vsd.equipment_serial_number.unwrap_or(0) as u64,
+        20,
+    );
+    push_string(&mut part_b, vsd.call_sign.as_deref().unwrap_or(""), 7);
+    if vsd.dimension_to_bow.is_none() && vsd.mothership_mmsi.is_some() {
+        // Auxiliary craft carry the mothership MMSI in place of the dimensions
+        push_u64(&mut part_b, vsd.mothership_mmsi.unwrap_or(0) as u64, 30);
+    } else {
+        push_dimensions(&mut part_b, vsd);
+    }
+    push_u64(&mut part_b, 0, 6);
+
+    (part_a, part_b)
+}
+
 impl VesselStaticData {
     /// Merge two data structures together. This is used to combine part A and B
     /// of class B AIVDM type 24 messages.
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 25: Single Slot Binary Message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SingleSlotBinaryMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -33,25 +34,52 @@ pub struct SingleSlotBinaryMessage {
     /// When 'addressed' flag is on this field contains the parsed destination MMSI.
     pub dest_mmsi: Option<u32>,
 
-    /// When 'addressed' flag is off and 'structured' flag on this field contains
-    /// application ID which consists of 10-bit DAC and 6-bit FID as in message types 6 and 8.
+    /// When 'structured' flag is on this field contains application ID which consists of
+    /// 10-bit DAC and 6-bit FID as in message types 6 and 8.
     pub app_id: Option<u16>,
 
     /// Data field of length 0-128 bits.
     pub data: BitVec,
+
+    /// Decoded application data if the message is structured and the DAC and FID combination
+    /// is supported.
+    pub application: Option<BinaryApplication>,
 }
 
 // -------------------------------------------------------------------------------------------------
 
 /// AIS VDM/VDO type 25: Single Slot Binary Message
-#[allow(clippy::collapsible_if)]
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    store: &NmeaParser,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
-    let addressed = pick_u64(&bv, 38, 1) != 0;
-    let structured = pick_u64(&bv, 39, 1) != 0;
+    let addressed = pick_u64(bv, 38, 1) != 0;
+    let structured = pick_u64(bv, 39, 1) != 0;
+
+    // Destination MMSI of addressed messages precedes the application ID and the data
+    let data_start = match (addressed, structured) {
+        (true, true) => 86,
+        (true, false) => 70,
+        (false, true) => 56,
+        (false, false) => 40,
+    };
+    if bv.len() < data_start {
+        return Err(ParseError::InvalidSentence(format!(
+            "AIS type 25 payload too short: {} bits",
+            bv.len()
+        )));
+    }
+    let app_id = if structured {
+        Some(pick_u64(bv, data_start - 16, 16) as u16)
+    } else {
+        None
+    };
+    let data = BitVec::from_bitslice(&bv[data_start..bv.len()]);
+    let application = app_id.and_then(|app_id| {
+        application::decode(store, app_id >> 6, (app_id & 0x3f) as u8, &data, addressed)
+    });
 
     Ok(ParsedMessage::SingleSlotBinaryMessage(
         SingleSlotBinaryMessage {
@@ -65,28 +93,9 @@ pub(crate) fn handle(
                     None
                 }
             },
-            app_id: {
-                if addressed {
-                    None
-                } else {
-                    if structured {
-                        Some(pick_u64(&bv, 70, 16) as u16)
-                    } else {
-                        None
-                    }
-                }
-            },
-            data: {
-                if addressed {
-                    BitVec::from_bitslice(&bv[70..bv.len()])
-                } else {
-                    if structured {
-                        BitVec::from_bitslice(&bv[86..bv.len()])
-                    } else {
-                        BitVec::from_bitslice(&bv[40..bv.len()])
-                    }
-                }
-            },
+            app_id,
+            data,
+            application,
         },
     ))
 }
@@ -132,7 +141,7 @@ mod test {
                     ParsedMessage::SingleSlotBinaryMessage(ssbm) => {
                         assert_eq!(ssbm.mmsi, 563648328);
                         assert_eq!(ssbm.dest_mmsi, None);
-                        assert_eq!(ssbm.app_id, Some(16424));
+                        assert_eq!(ssbm.app_id, Some(134));
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -170,4 +179,14 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type25_too_short() {
+        let mut p = NmeaParser::new();
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,I000004,0*6B")
+                .map_err(|e| e.to_string()),
+            Err("Invalid NMEA sentence: AIS type 25 payload too short: 42 bits".into())
+        );
+    }
 }
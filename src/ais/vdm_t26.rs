@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 26: Multiple Slot Binary Message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultipleSlotBinaryMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -33,13 +34,17 @@ pub struct MultipleSlotBinaryMessage {
     /// When 'addressed' flag is on this field contains the parsed destination MMSI.
     pub dest_mmsi: Option<u32>,
 
-    /// When 'addressed' flag is off and 'structured' flag on this field contains
-    /// application ID which consists of 10-bit DAC and 6-bit FID as in message types 6 and 8.
+    /// When 'structured' flag is on this field contains application ID which consists of
+    /// 10-bit DAC and 6-bit FID as in message types 6 and 8.
     pub app_id: Option<u16>,
 
     /// Data field of length 0-1004 bits.
     pub data: BitVec,
 
+    /// Decoded application data if the message is structured and the DAC and FID combination
+    /// is supported.
+    pub application: Option<BinaryApplication>,
+
     /// Radio status
     pub radio: u32,
 }
@@ -47,14 +52,37 @@ pub struct MultipleSlotBinaryMessage {
 // -------------------------------------------------------------------------------------------------
 
 /// AIS VDM/VDO type 26: Multiple Slot Binary Message
-#[allow(clippy::collapsible_if)]
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    store: &NmeaParser,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
-    let addressed = pick_u64(&bv, 38, 1) != 0;
-    let structured = pick_u64(&bv, 39, 1) != 0;
+    let addressed = pick_u64(bv, 38, 1) != 0;
+    let structured = pick_u64(bv, 39, 1) != 0;
+
+    // Destination MMSI of addressed messages precedes the application ID and the data
+    let data_start = match (addressed, structured) {
+        (true, true) => 86,
+        (true, false) => 70,
+        (false, true) => 56,
+        (false, false) => 40,
+    };
+    if bv.len() < data_start + 20 {
+        return Err(ParseError::InvalidSentence(format!(
+            "AIS type 26 payload too short: {} bits",
+            bv.len()
+        )));
+    }
+    let app_id = if structured {
+        Some(pick_u64(bv, data_start - 16, 16) as u16)
+    } else {
+        None
+    };
+    let data = BitVec::from_bitslice(&bv[data_start..(bv.len() - 20)]);
+    let application = app_id.and_then(|app_id| {
+        application::decode(store, app_id >> 6, (app_id & 0x3f) as u8, &data, addressed)
+    });
 
     Ok(ParsedMessage::MultipleSlotBinaryMessage(
         MultipleSlotBinaryMessage {
@@ -68,28 +96,9 @@ pub(crate) fn handle(
                     None
                 }
             },
-            app_id: {
-                if addressed {
-                    None
-                } else {
-                    if structured {
-                        Some(pick_u64(&bv, 70, 16) as u16)
-                    } else {
-                        None
-                    }
-                }
-            },
-            data: {
-                if addressed {
-                    BitVec::from_bitslice(&bv[70..(bv.len() - 20)])
-                } else {
-                    if structured {
-                        BitVec::from_bitslice(&bv[86..(bv.len() - 20)])
-                    } else {
-                        BitVec::from_bitslice(&bv[40..(bv.len() - 20)])
-                    }
-                }
-            },
+            app_id,
+            data,
+            application,
             radio: { pick_u64(&bv, bv.len() - 20, 20) as u32 },
         },
     ))
@@ -112,7 +121,9 @@ mod test {
                     ParsedMessage::MultipleSlotBinaryMessage(msbm) => {
                         assert_eq!(msbm.mmsi, 137920605);
                         assert_eq!(msbm.dest_mmsi, Some(838351848));
-                        assert_eq!(msbm.app_id, None);
+                        assert_eq!(msbm.app_id, Some(23587));
+                        assert_eq!(msbm.data.len(), 150);
+                        assert_eq!(msbm.radio, 4096);
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
@@ -127,4 +138,14 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_vdm_type26_too_short() {
+        let mut p = NmeaParser::new();
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,J0,0*5C")
+                .map_err(|e| e.to_string()),
+            Err("Invalid NMEA sentence: AIS type 26 payload too short: 12 bits".into())
+        );
+    }
 }
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 4: Base Station Report
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BaseStationReport {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -117,6 +118,45 @@ pub(crate) fn handle(
     }))
 }
 
+/// Encode `BaseStationReport` as AIS VDM/VDO type 4 (Base Station Report) or type 11 (UTC/Date
+/// Response) depending on the given message type.
+pub(crate) fn encode(bsr: &BaseStationReport, message_type: u8) -> BitVec {
+    let mut bv = BitVec::with_capacity(168);
+    push_u64(&mut bv, message_type as u64, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, bsr.mmsi as u64, 30);
+    match bsr.timestamp {
+        Some(ts) => {
+            push_u64(&mut bv, ts.year() as u64, 14);
+            push_u64(&mut bv, ts.month() as u64, 4);
+            push_u64(&mut bv, ts.day() as u64, 5);
+            push_u64(&mut bv, ts.hour() as u64, 5);
+            push_u64(&mut bv, ts.minute() as u64, 6);
+            push_u64(&mut bv, ts.second() as u64, 6);
+        }
+        None => {
+            push_u64(&mut bv, 0, 14);
+            push_u64(&mut bv, 0, 4);
+            push_u64(&mut bv, 0, 5);
+            push_u64(&mut bv, 24, 5);
+            push_u64(&mut bv, 60, 6);
+            push_u64(&mut bv, 60, 6);
+        }
+    }
+    push_u64(&mut bv, bsr.high_position_accuracy as u64, 1);
+    push_longitude(&mut bv, bsr.longitude);
+    push_latitude(&mut bv, bsr.latitude);
+    push_u64(
+        &mut bv,
+        bsr.position_fix_type.map(|t| t.to_value()).unwrap_or(0) as u64,
+        4,
+    );
+    push_u64(&mut bv, 0, 10);
+    push_u64(&mut bv, bsr.raim_flag as u64, 1);
+    push_u64(&mut bv, bsr.radio_status as u64, 19);
+    bv
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
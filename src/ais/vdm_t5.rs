@@ -19,6 +19,7 @@ use super::*;
 pub(crate) fn handle(
     bv: &BitVec,
     _station: Station,
+    store: &NmeaParser,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
     Ok(ParsedMessage::VesselStaticData(VesselStaticData {
@@ -69,7 +70,7 @@ pub(crate) fn handle(
                 _ => Some(PositionFixType::new(raw)),
             }
         },
-        eta: pick_eta(&bv, 274)?,
+        eta: pick_eta(&bv, 274, store.feed_time)?,
         draught10: Some(pick_u64(&bv, 294, 8) as u8),
         destination: {
             let raw = pick_string(&bv, 302, 20);
@@ -82,6 +83,34 @@ pub(crate) fn handle(
     }))
 }
 
+/// Encode `VesselStaticData` as AIS VDM/VDO type 5: Ship static voyage related data
+pub(crate) fn encode(vsd: &VesselStaticData) -> BitVec {
+    let mut bv = BitVec::with_capacity(424);
+    push_u64(&mut bv, 5, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, vsd.mmsi as u64, 30);
+    push_u64(&mut bv, vsd.ais_version_indicator as u64, 2);
+    push_u64(&mut bv, vsd.imo_number.unwrap_or(0) as u64, 30);
+    push_string(&mut bv, vsd.call_sign.as_deref().unwrap_or(""), 7);
+    push_string(&mut bv, vsd.name.as_deref().unwrap_or(""), 20);
+    push_u64(
+        &mut bv,
+        ship_and_cargo_type(vsd.ship_type, vsd.cargo_type) as u64,
+        8,
+    );
+    push_dimensions(&mut bv, vsd);
+    push_u64(
+        &mut bv,
+        vsd.position_fix_type.map(|t| t.to_value()).unwrap_or(0) as u64,
+        4,
+    );
+    push_eta(&mut bv, vsd.eta);
+    push_u64(&mut bv, vsd.draught10.unwrap_or(0) as u64, 8);
+    push_string(&mut bv, vsd.destination.as_deref().unwrap_or(""), 20);
+    push_u64(&mut bv, 0, 2);
+    bv
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
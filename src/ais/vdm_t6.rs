@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 6: Binary Addressed Message
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinaryAddressedMessage {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -44,28 +45,38 @@ pub struct BinaryAddressedMessage {
 
     /// Functional ID, FID (6 bits)
     pub fid: u8,
-    // TODO: data (depending on DAC and FID
+
+    /// Application specific data field of length 0-920 bits. Its content depends on DAC and FID.
+    pub data: BitVec,
+
+    /// Decoded application data if the DAC and FID combination is supported.
+    pub application: Option<BinaryApplication>,
 }
 
 impl LatLon for BinaryAddressedMessage {
     fn latitude(&self) -> Option<f64> {
-        None // TODO: depends on data
+        self.application.as_ref().and_then(|a| a.latitude())
     }
 
     fn longitude(&self) -> Option<f64> {
-        None // TODO: depends on data
+        self.application.as_ref().and_then(|a| a.longitude())
     }
 }
 
 // -------------------------------------------------------------------------------------------------
 
-/// AIS VDM/VDO type 6: Binary Addressed Message. Implementation of the 920-bit data field is
-/// unimplemented currently.
+/// AIS VDM/VDO type 6: Binary Addressed Message. The data field is returned as raw bits and
+/// additionally decoded if the application is supported.
 pub(crate) fn handle(
     bv: &BitVec,
     station: Station,
+    store: &NmeaParser,
     own_vessel: bool,
 ) -> Result<ParsedMessage, ParseError> {
+    let dac = pick_u64(bv, 72, 10) as u16;
+    let fid = pick_u64(bv, 82, 6) as u8;
+    let data: BitVec = bv.iter().skip(88).collect();
+    let application = application::decode(store, dac, fid, &data, true);
     Ok(ParsedMessage::BinaryAddressedMessage(
         BinaryAddressedMessage {
             own_vessel: { own_vessel },
@@ -74,8 +85,10 @@ pub(crate) fn handle(
             sequence_number: { pick_u64(&bv, 38, 2) as u8 },
             destination_mmsi: { pick_u64(&bv, 40, 30) as u32 },
             retransmit_flag: { pick_u64(&bv, 70, 1) != 0 },
-            dac: { pick_u64(&bv, 72, 10) as u16 },
-            fid: { pick_u64(&bv, 82, 6) as u8 }, // TODO: data (depending on DAC and FID
+            dac,
+            fid,
+            data,
+            application,
         },
     ))
 }
@@ -100,7 +113,7 @@ mod test {
                         assert_eq!(bam.retransmit_flag, false);
                         assert_eq!(bam.dac, 669);
                         assert_eq!(bam.fid, 11);
-                        // TODO: check data
+                        assert_eq!(bam.data.len(), 48);
                     }
                     ParsedMessage::Incomplete => {
                         assert!(false);
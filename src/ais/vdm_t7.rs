@@ -16,6 +16,105 @@ limitations under the License.
 
 use super::*;
 
-// Message type 13 is a receipt acknowledgement to senders of previous messages of type 12. 
-// The message layout is identical to a type 7 Binary Acknowledge.
+// -------------------------------------------------------------------------------------------------
 
+/// Type 7: Binary Acknowledge
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryAcknowledge {
+    /// True if the data is about own vessel, false if about other.
+    pub own_vessel: bool,
+
+    /// AIS station type.
+    pub station: Station,
+
+    /// Source MMSI (30 bits)
+    pub mmsi: u32,
+
+    /// MMSI number 1 (30 bits)
+    pub mmsi1: u32,
+
+    /// MMSI sequence
+    pub mmsi1_seq: u8,
+
+    /// MMSI number 2 (30 bits)
+    pub mmsi2: u32,
+
+    /// MMSI sequence
+    pub mmsi2_seq: u8,
+
+    /// MMSI number 3 (30 bits)
+    pub mmsi3: u32,
+
+    /// MMSI sequence
+    pub mmsi3_seq: u8,
+
+    /// MMSI number 4 (30 bits)
+    pub mmsi4: u32,
+
+    /// MMSI sequence
+    pub mmsi4_seq: u8,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// AIS VDM/VDO type 7: Binary Acknowledge. The message layout is identical to type 13
+/// Safety-Related Acknowledgment.
+pub(crate) fn handle(
+    bv: &BitVec,
+    station: Station,
+    own_vessel: bool,
+) -> Result<ParsedMessage, ParseError> {
+    Ok(ParsedMessage::BinaryAcknowledge(BinaryAcknowledge {
+        own_vessel: { own_vessel },
+        station: { station },
+        mmsi: { pick_u64(&bv, 8, 30) as u32 },
+        mmsi1: { pick_u64(&bv, 40, 30) as u32 },
+        mmsi1_seq: { pick_u64(&bv, 70, 2) as u8 },
+        mmsi2: { pick_u64(&bv, 72, 30) as u32 },
+        mmsi2_seq: { pick_u64(&bv, 102, 2) as u8 },
+        mmsi3: { pick_u64(&bv, 104, 30) as u32 },
+        mmsi3_seq: { pick_u64(&bv, 134, 2) as u8 },
+        mmsi4: { pick_u64(&bv, 136, 30) as u32 },
+        mmsi4_seq: { pick_u64(&bv, 166, 2) as u8 },
+    }))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vdm_type7() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("!AIVDM,1,1,,A,702R5`hwCjq8,0*6B") {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::BinaryAcknowledge(ba) => {
+                        assert_eq!(ba.mmsi, 2655651);
+                        assert_eq!(ba.mmsi1, 265538450);
+                        assert_eq!(ba.mmsi1_seq, 0);
+                        assert_eq!(ba.mmsi2, 0);
+                        assert_eq!(ba.mmsi2_seq, 0);
+                        assert_eq!(ba.mmsi3, 0);
+                        assert_eq!(ba.mmsi3_seq, 0);
+                        assert_eq!(ba.mmsi4, 0);
+                        assert_eq!(ba.mmsi4_seq, 0);
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+}
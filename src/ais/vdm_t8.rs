@@ -0,0 +1,108 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Type 8: Binary Broadcast Message
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryBroadcastMessage {
+    /// True if the data is about own vessel, false if about other.
+    pub own_vessel: bool,
+
+    /// AIS station type.
+    pub station: Station,
+
+    /// Source MMSI (30 bits)
+    pub mmsi: u32,
+
+    /// Designated area code, DAC (10 bits)
+    pub dac: u16,
+
+    /// Functional ID, FID (6 bits)
+    pub fid: u8,
+
+    /// Application specific data field of length 0-952 bits. Its content depends on DAC and FID.
+    pub data: BitVec,
+
+    /// Decoded application data if the DAC and FID combination is supported.
+    pub application: Option<BinaryApplication>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// AIS VDM/VDO type 8: Binary Broadcast Message. The data field is returned as raw bits and
+/// additionally decoded if the application is supported.
+pub(crate) fn handle(
+    bv: &BitVec,
+    station: Station,
+    store: &NmeaParser,
+    own_vessel: bool,
+) -> Result<ParsedMessage, ParseError> {
+    let dac = pick_u64(bv, 40, 10) as u16;
+    let fid = pick_u64(bv, 50, 6) as u8;
+    let data: BitVec = bv.iter().skip(56).collect();
+    let application = application::decode(store, dac, fid, &data, false);
+    Ok(ParsedMessage::BinaryBroadcastMessage(
+        BinaryBroadcastMessage {
+            own_vessel: { own_vessel },
+            station: { station },
+            mmsi: { pick_u64(&bv, 8, 30) as u32 },
+            dac,
+            fid,
+            data,
+            application,
+        },
+    ))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vdm_type8() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence(
+            "!AIVDM,1,1,,A,85Mwp`1Kf3aCnsNvBWLi=wQuNhA5t43N`5nCuI=p<IBfVqnMgPGs,0*47",
+        ) {
+            Ok(ps) => {
+                match ps {
+                    // The expected result
+                    ParsedMessage::BinaryBroadcastMessage(bbm) => {
+                        assert_eq!(bbm.mmsi, 366999712);
+                        assert_eq!(bbm.dac, 366);
+                        assert_eq!(bbm.fid, 56);
+                        assert_eq!(bbm.data.len(), 312 - 56);
+                    }
+                    ParsedMessage::Incomplete => {
+                        assert!(false);
+                    }
+                    _ => {
+                        assert!(false);
+                    }
+                }
+            }
+            Err(e) => {
+                assert_eq!(e.to_string(), "OK");
+            }
+        }
+    }
+}
@@ -20,6 +20,7 @@ use super::*;
 
 /// Type 9: Standard SAR Aircraft Position Report
 #[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StandardSarAircraftPositionReport {
     /// True if the data is about own vessel, false if about other.
     pub own_vessel: bool,
@@ -145,6 +146,28 @@ pub(crate) fn handle(
     ))
 }
 
+/// Encode `StandardSarAircraftPositionReport` as AIS VDM/VDO type 9
+pub(crate) fn encode(sar: &StandardSarAircraftPositionReport) -> BitVec {
+    let mut bv = BitVec::with_capacity(168);
+    push_u64(&mut bv, 9, 6);
+    push_u64(&mut bv, 0, 2);
+    push_u64(&mut bv, sar.mmsi as u64, 30);
+    push_u64(&mut bv, sar.altitude.unwrap_or(4095) as u64, 12);
+    push_u64(&mut bv, sar.sog_knots.unwrap_or(1023) as u64, 10);
+    push_u64(&mut bv, sar.high_position_accuracy as u64, 1);
+    push_longitude(&mut bv, sar.longitude);
+    push_latitude(&mut bv, sar.latitude);
+    push_cog(&mut bv, sar.cog);
+    push_u64(&mut bv, sar.timestamp_seconds as u64, 6);
+    push_u64(&mut bv, sar.regional as u64, 8);
+    push_u64(&mut bv, !sar.dte as u64, 1);
+    push_u64(&mut bv, 0, 3);
+    push_u64(&mut bv, sar.assigned as u64, 1);
+    push_u64(&mut bv, sar.raim_flag as u64, 1);
+    push_u64(&mut bv, sar.radio_status as u64, 20);
+    bv
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -0,0 +1,360 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Command-line tool which decodes NMEA 0183 sentences read from a file or standard input and
+//! writes one JSON object per decoded message or error to standard output.
+
+use nmea_parser::*;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::process::exit;
+
+// -------------------------------------------------------------------------------------------------
+
+const USAGE: &str = "\
+Usage: nmea-decode [OPTIONS] [FILE]
+
+Decode NMEA 0183 sentences from FILE or standard input into JSON lines.
+
+Options:
+  -t, --type <TYPES>   Output only the given comma separated sentence types (e.g. GGA,VDM)
+  -m, --mmsi <MMSIS>   Output only AIS messages of the given comma separated MMSIs
+  -s, --strict         Enable strict mode of the parser
+  -q, --quiet          Don't print summary to standard error
+  -h, --help           Print this help";
+
+/// Command line options
+#[derive(Default)]
+struct Options {
+    file: Option<String>,
+    types: Option<HashSet<String>>,
+    mmsis: Option<HashSet<u64>>,
+    strict: bool,
+    quiet: bool,
+}
+
+/// Counts of processed lines, messages and errors
+#[derive(Default)]
+struct Summary {
+    lines: usize,
+    messages: usize,
+    unsupported: usize,
+    corrupted: usize,
+    invalid: usize,
+}
+
+impl Summary {
+    /// Count the given parse result.
+    fn add(&mut self, result: &Result<ParsedMessage, ParseError>) {
+        match result {
+            Ok(_) => self.messages += 1,
+            Err(ParseError::UnsupportedSentenceType(_)) => self.unsupported += 1,
+            Err(ParseError::CorruptedSentence(_)) => self.corrupted += 1,
+            Err(ParseError::InvalidSentence(_)) => self.invalid += 1,
+        }
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "lines: {}, messages: {}, errors: {} (unsupported: {}, corrupted: {}, invalid: {})",
+            self.lines,
+            self.messages,
+            self.unsupported + self.corrupted + self.invalid,
+            self.unsupported,
+            self.corrupted,
+            self.invalid
+        )
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("nmea-decode: {}\n\n{}", e, USAGE);
+            exit(2);
+        }
+    };
+
+    let input: Box<dyn BufRead> = match &options.file {
+        Some(path) if path != "-" => match File::open(path) {
+            Ok(f) => Box::new(BufReader::new(f)),
+            Err(e) => {
+                eprintln!("nmea-decode: {}: {}", path, e);
+                exit(1);
+            }
+        },
+        _ => Box::new(BufReader::new(std::io::stdin())),
+    };
+
+    let stdout = std::io::stdout();
+    let mut output = BufWriter::new(stdout.lock());
+    let mut summary = Summary::default();
+    let mut parser = NmeaParser::new();
+    parser.set_strict_mode(options.strict);
+    for record in NmeaReader::with_parser(input, parser) {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                eprintln!("nmea-decode: {}", e);
+                exit(1);
+            }
+        };
+        summary.lines += record.lines.len();
+        summary.add(&record.result);
+        if let Some(value) = record_to_json(&record, &options) {
+            if writeln!(output, "{}", value).is_err() {
+                // Output closed, e.g. piped to head
+                break;
+            }
+        }
+    }
+    output.flush().ok();
+
+    if !options.quiet {
+        eprintln!("{}", summary);
+    }
+}
+
+/// Parse command line arguments.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            }
+            "-s" | "--strict" => options.strict = true,
+            "-q" | "--quiet" => options.quiet = true,
+            "-t" | "--type" => {
+                let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+                options.types = Some(value.split(',').map(|s| s.trim().to_uppercase()).collect());
+            }
+            "-m" | "--mmsi" => {
+                let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+                let mut mmsis = HashSet::new();
+                for s in value.split(',') {
+                    mmsis.insert(
+                        s.trim()
+                            .parse::<u64>()
+                            .map_err(|_| format!("Invalid MMSI: {}", s))?,
+                    );
+                }
+                options.mmsis = Some(mmsis);
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("Unknown option: {}", arg));
+            }
+            _ => {
+                if options.file.is_some() {
+                    return Err(format!("Unexpected argument: {}", arg));
+                }
+                options.file = Some(arg);
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Convert the record to JSON object containing the line number, raw sentences and either the
+/// message type and data or the error. `None` is returned if the record is filtered out.
+fn record_to_json(record: &NmeaRecord, options: &Options) -> Option<Value> {
+    let (line, last) = record.lines.last()?;
+    if let Some(types) = &options.types {
+        if !types.contains(&sentence_type(last)) {
+            return None;
+        }
+    }
+
+    let mut value = match &record.result {
+        Ok(message) => {
+            serde_json::to_value(message).unwrap_or_else(|e| json!({ "error": e.to_string() }))
+        }
+        Err(e) => json!({ "error": e.to_string() }),
+    };
+    if let Some(mmsis) = &options.mmsis {
+        message_mmsi(&value).filter(|mmsi| mmsis.contains(mmsi))?;
+    }
+
+    if let Value::Object(map) = &mut value {
+        let first_line = record.lines.first().map_or(*line, |(n, _)| *n);
+        map.insert("line".into(), json!(first_line));
+        map.insert(
+            "sentences".into(),
+            json!(record
+                .lines
+                .iter()
+                .map(|(_, s)| s)
+                .collect::<Vec<&String>>()),
+        );
+    }
+    Some(value)
+}
+
+/// Return the three letter sentence type (e.g. GGA or VDM) of the given raw sentence. A leading
+/// TAG block is skipped.
+fn sentence_type(sentence: &str) -> String {
+    let sentence = match sentence.strip_prefix('\\') {
+        Some(rest) => rest.find('\\').map_or("", |end| &rest[end + 1..]),
+        None => sentence,
+    };
+    let address = sentence
+        .trim_start_matches(['$', '!'])
+        .split(',')
+        .next()
+        .unwrap_or("");
+    let start = address.len().saturating_sub(3);
+    address.get(start..).unwrap_or("").to_uppercase()
+}
+
+/// Return the MMSI of the AIS message serialized to the given JSON value.
+fn message_mmsi(value: &Value) -> Option<u64> {
+    let data = value.get("data")?;
+    data.get("mmsi")
+        .or_else(|| data.get("dynamic_data").and_then(|d| d.get("mmsi")))
+        .and_then(|mmsi| mmsi.as_u64())
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    fn record(sentence: &str) -> NmeaRecord {
+        let mut p = NmeaParser::new();
+        NmeaRecord {
+            result: p.parse_sentence(sentence),
+            lines: vec![(3, sentence.to_string())],
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        match parse_args(args(&[
+            "-s", "--type", "gga, VDM", "-m", "1,2", "-q", "log.nmea",
+        ])) {
+            Ok(options) => {
+                assert_eq!(options.file, Some("log.nmea".into()));
+                assert_eq!(
+                    options.types,
+                    Some(
+                        ["GGA".to_string(), "VDM".to_string()]
+                            .iter()
+                            .cloned()
+                            .collect()
+                    )
+                );
+                assert_eq!(options.mmsis, Some([1, 2].iter().cloned().collect()));
+                assert!(options.strict);
+                assert!(options.quiet);
+            }
+            Err(e) => {
+                assert_eq!(e, "OK");
+            }
+        }
+        match parse_args(args(&["-"])) {
+            Ok(options) => {
+                assert_eq!(options.file, Some("-".into()));
+                assert_eq!(options.types, None);
+                assert!(!options.strict);
+            }
+            Err(e) => {
+                assert_eq!(e, "OK");
+            }
+        }
+        assert_eq!(
+            parse_args(args(&["--type"])).err(),
+            Some("Missing value for --type".into())
+        );
+        assert_eq!(
+            parse_args(args(&["-m", "1,x"])).err(),
+            Some("Invalid MMSI: x".into())
+        );
+        assert_eq!(
+            parse_args(args(&["-x"])).err(),
+            Some("Unknown option: -x".into())
+        );
+        assert_eq!(
+            parse_args(args(&["a", "b"])).err(),
+            Some("Unexpected argument: b".into())
+        );
+    }
+
+    #[test]
+    fn test_record_to_json() {
+        let vdm = record("!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40");
+        let value = record_to_json(&vdm, &Options::default()).unwrap();
+        assert_eq!(value["type"], "VesselDynamicData");
+        assert_eq!(value["data"]["mmsi"], 563808000);
+        assert_eq!(value["line"], 3);
+        assert_eq!(
+            value["sentences"],
+            json!(["!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40"])
+        );
+
+        // Errors are written as objects too
+        let corrupted = record("!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*41");
+        let value = record_to_json(&corrupted, &Options::default()).unwrap();
+        assert!(value["error"].is_string());
+        assert_eq!(value["line"], 3);
+
+        // Filters
+        let mut options = Options {
+            types: Some(["GGA".to_string()].iter().cloned().collect()),
+            ..Default::default()
+        };
+        assert_eq!(record_to_json(&vdm, &options), None);
+        options.types = Some(["VDM".to_string()].iter().cloned().collect());
+        assert!(record_to_json(&vdm, &options).is_some());
+        options.mmsis = Some([1].iter().cloned().collect());
+        assert_eq!(record_to_json(&vdm, &options), None);
+        options.mmsis = Some([563808000].iter().cloned().collect());
+        assert!(record_to_json(&vdm, &options).is_some());
+        assert_eq!(record_to_json(&corrupted, &options), None);
+    }
+
+    #[test]
+    fn test_sentence_type() {
+        assert_eq!(sentence_type("$GPGGA,123519,4807.038,N"), "GGA");
+        assert_eq!(
+            sentence_type("!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40"),
+            "VDM"
+        );
+        assert_eq!(sentence_type("$gpzda,072914.00"), "ZDA");
+        assert_eq!(
+            sentence_type("\\s:2573535,c:1671533231*08\\!AIVDM,1,1,,A,38Id7,0*40"),
+            "VDM"
+        );
+        assert_eq!(sentence_type("\\s:2573535"), "");
+        assert_eq!(sentence_type(""), "");
+    }
+}
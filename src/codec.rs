@@ -0,0 +1,284 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Async NMEA codec for tokio
+
+use super::*;
+use bytes::{Buf, BytesMut};
+use tokio::io::AsyncRead;
+use tokio_util::codec::{Decoder, FramedRead};
+
+/// Stream of parsed messages read from an `AsyncRead` source.
+pub type NmeaStream<R> = FramedRead<R, NmeaCodec>;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decoder which frames a byte stream into NMEA sentences and parses them with `NmeaParser`.
+/// The parser keeps the state of multi-sentence messages between frames, so incomplete messages
+/// are not emitted. Parse errors are returned as items so that a single bad sentence doesn't
+/// terminate the stream. CR, LF and CR LF line endings are accepted and blank lines are skipped.
+pub struct NmeaCodec {
+    parser: NmeaParser,
+    max_line_length: usize,
+    discarding: bool,
+}
+
+impl NmeaCodec {
+    /// Construct a codec with a new `NmeaParser`.
+    pub fn new() -> NmeaCodec {
+        NmeaCodec::with_parser(NmeaParser::new())
+    }
+
+    /// Construct a codec which uses the given parser.
+    pub fn with_parser(parser: NmeaParser) -> NmeaCodec {
+        NmeaCodec {
+            parser,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            discarding: false,
+        }
+    }
+
+    /// Construct a stream which reads and parses sentences from the given reader.
+    pub fn stream<R: AsyncRead>(reader: R) -> NmeaStream<R> {
+        FramedRead::new(reader, NmeaCodec::new())
+    }
+
+    /// Construct a stream which reads sentences from the given reader and parses them with the
+    /// given parser.
+    pub fn stream_with_parser<R: AsyncRead>(reader: R, parser: NmeaParser) -> NmeaStream<R> {
+        FramedRead::new(reader, NmeaCodec::with_parser(parser))
+    }
+
+    /// Set the maximum accepted line length in bytes.
+    pub fn set_max_line_length(&mut self, max_line_length: usize) {
+        self.max_line_length = max_line_length;
+    }
+
+    /// Return a reference to the parser.
+    pub fn parser(&self) -> &NmeaParser {
+        &self.parser
+    }
+
+    /// Return a mutable reference to the parser.
+    pub fn parser_mut(&mut self) -> &mut NmeaParser {
+        &mut self.parser
+    }
+
+    /// Return the inner parser.
+    pub fn into_parser(self) -> NmeaParser {
+        self.parser
+    }
+
+    /// Parse one line without line terminator. `None` is returned for blank lines and sentences
+    /// of incomplete messages.
+    fn parse_line(&mut self, raw: &[u8]) -> Option<Result<ParsedMessage, ParseError>> {
+        if raw
+            .iter()
+            .any(|b| !(b.is_ascii_graphic() || *b == b' ' || *b == b'\t'))
+        {
+            return Some(Err(ParseError::InvalidSentence(
+                "Binary data in sentence".to_string(),
+            )));
+        }
+        let line = String::from_utf8_lossy(raw);
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        match self.parser.parse_sentence(line) {
+            Ok(ParsedMessage::Incomplete) => None,
+            result => Some(result),
+        }
+    }
+}
+
+impl Default for NmeaCodec {
+    fn default() -> Self {
+        NmeaCodec::new()
+    }
+}
+
+impl Decoder for NmeaCodec {
+    type Item = Result<ParsedMessage, ParseError>;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            let end = match buf.iter().position(|b| *b == b'\n' || *b == b'\r') {
+                Some(end) => end,
+                None => {
+                    // Drop the beginning of an over-long line and ignore the rest of it
+                    if buf.len() > self.max_line_length {
+                        buf.clear();
+                        if !self.discarding {
+                            self.discarding = true;
+                            return Ok(Some(Err(ParseError::InvalidSentence(format!(
+                                "Line exceeds maximum length of {} bytes",
+                                self.max_line_length
+                            )))));
+                        }
+                    }
+                    return Ok(None);
+                }
+            };
+            let line = buf.split_to(end);
+            buf.advance(1);
+            if self.discarding {
+                self.discarding = false;
+                continue;
+            }
+            if line.len() > self.max_line_length {
+                return Ok(Some(Err(ParseError::InvalidSentence(format!(
+                    "Line exceeds maximum length of {} bytes",
+                    self.max_line_length
+                )))));
+            }
+            if let Some(result) = self.parse_line(&line) {
+                return Ok(Some(result));
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(item) = self.decode(buf)? {
+            return Ok(Some(item));
+        }
+
+        // Parse the last line even if it isn't terminated
+        let line = buf.split_to(buf.len());
+        if self.discarding {
+            self.discarding = false;
+            return Ok(None);
+        }
+        Ok(self.parse_line(&line))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nmea_codec() {
+        let mut codec = NmeaCodec::new();
+        let mut buf = BytesMut::from(
+            "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40\r\n\
+             \r\n\
+             !AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C\r\
+             garbage\n\
+             !AIVDM,2,2,1,A,88888888880,2*25\n\
+             $GPZDA,0729",
+        );
+
+        match codec.decode(&mut buf) {
+            Ok(Some(Ok(ParsedMessage::VesselDynamicData(_)))) => {}
+            _ => assert!(false),
+        }
+        match codec.decode(&mut buf) {
+            Ok(Some(Err(_))) => {}
+            _ => assert!(false),
+        }
+        match codec.decode(&mut buf) {
+            Ok(Some(Ok(ParsedMessage::VesselStaticData(vsd)))) => {
+                assert_eq!(vsd.mmsi, 351759000);
+            }
+            _ => assert!(false),
+        }
+
+        // Partial sentence remains buffered until the rest arrives
+        match codec.decode(&mut buf) {
+            Ok(None) => {}
+            _ => assert!(false),
+        }
+        buf.extend_from_slice(b"14.00,31,05,2018,-03,00");
+        match codec.decode_eof(&mut buf) {
+            Ok(Some(Ok(ParsedMessage::Zda(zda)))) => {
+                assert_eq!(
+                    zda.timestamp_utc,
+                    Some(Utc.ymd(2018, 5, 31).and_hms(7, 29, 14))
+                );
+            }
+            _ => assert!(false),
+        }
+        match codec.decode_eof(&mut buf) {
+            Ok(None) => {}
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_nmea_codec_long_line() {
+        let mut codec = NmeaCodec::new();
+        codec.set_max_line_length(20);
+        let mut buf = BytesMut::from("$GPZDA,072914.00,31,05");
+        match codec.decode(&mut buf) {
+            Ok(Some(Err(e))) => {
+                assert_eq!(
+                    e.to_string(),
+                    "Invalid NMEA sentence: Line exceeds maximum length of 20 bytes"
+                );
+            }
+            _ => assert!(false),
+        }
+
+        // The rest of the line is discarded
+        buf.extend_from_slice(b",2018,-03,00\n");
+        match codec.decode(&mut buf) {
+            Ok(None) => assert!(buf.is_empty()),
+            _ => assert!(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nmea_stream() {
+        use tokio_stream::StreamExt;
+
+        let mut parser = NmeaParser::new();
+        parser.set_clock(Clock::Fixed(Utc.ymd(2021, 1, 1).and_hms(12, 0, 0)));
+        let data: &[u8] = b"!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40\r\n\
+            !AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C\r\n\
+            !AIVDM,2,2,1,A,88888888880,2*25\r\n\
+            $GPZDA,072914.00,31,05,2018,-03,00";
+        let mut stream = NmeaCodec::stream_with_parser(data, parser);
+
+        match stream.next().await {
+            Some(Ok(Ok(ParsedMessage::VesselDynamicData(vdd)))) => {
+                assert_eq!(vdd.mmsi, 563808000);
+            }
+            _ => assert!(false),
+        }
+        match stream.next().await {
+            Some(Ok(Ok(ParsedMessage::VesselStaticData(vsd)))) => {
+                assert_eq!(vsd.mmsi, 351759000);
+            }
+            _ => assert!(false),
+        }
+        match stream.next().await {
+            Some(Ok(Ok(ParsedMessage::Zda(_)))) => {}
+            _ => assert!(false),
+        }
+        assert!(stream.next().await.is_none());
+
+        // The parser given to the stream is used
+        let codec = stream.decoder();
+        assert_eq!(
+            codec.parser().clock(),
+            Clock::Fixed(Utc.ymd(2021, 1, 1).and_hms(12, 0, 0))
+        );
+    }
+}
@@ -0,0 +1,409 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Collision risk estimation based on closest point of approach (CPA)
+
+use super::*;
+use ais::VesselDynamicData;
+use gnss::RmcData;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Speed below which a vessel is considered stationary and its course is ignored (knots).
+const STATIONARY_SOG_KNOTS: f64 = 0.05;
+
+/// Position and motion of a vessel used in CPA/TCPA computation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShipMotion {
+    /// Latitude in degrees
+    pub latitude: f64,
+
+    /// Longitude in degrees
+    pub longitude: f64,
+
+    /// Speed over ground in knots, `None` if not available
+    pub sog_knots: Option<f64>,
+
+    /// Course over ground in degrees, `None` if not available
+    pub cog: Option<f64>,
+}
+
+impl ShipMotion {
+    /// Construct motion from AIS dynamic data. Returns `None` if the position is not available.
+    pub fn from_dynamic_data(vdd: &VesselDynamicData) -> Option<ShipMotion> {
+        Some(ShipMotion {
+            latitude: vdd.latitude?,
+            longitude: vdd.longitude?,
+            sog_knots: vdd.sog_knots,
+            cog: vdd.cog,
+        })
+    }
+
+    /// Construct motion from GNSS RMC data. Returns `None` if the position is not available or
+    /// the fix is void.
+    pub fn from_rmc(rmc: &RmcData) -> Option<ShipMotion> {
+        if rmc.status_active != Some(true) {
+            return None;
+        }
+        Some(ShipMotion {
+            latitude: rmc.latitude?,
+            longitude: rmc.longitude?,
+            sog_knots: rmc.sog_knots,
+            cog: rmc.bearing,
+        })
+    }
+
+    /// Return velocity as east and north components in knots. Course is not needed when the
+    /// vessel is stationary. Returns `None` if the velocity can't be determined.
+    fn velocity(&self) -> Option<(f64, f64)> {
+        let sog = self.sog_knots?;
+        if sog < STATIONARY_SOG_KNOTS {
+            return Some((0.0, 0.0));
+        }
+        let cog = self.cog?.to_radians();
+        Some((sog * cog.sin(), sog * cog.cos()))
+    }
+
+    /// Compute CPA distance in nautical miles and TCPA in minutes relative to the given target.
+    /// Negative TCPA means that the closest point of approach has already been passed. Returns
+    /// `None` if the speed or course of either vessel is not available.
+    pub fn cpa(&self, target: &ShipMotion) -> Option<(f64, f64)> {
+        let (own_ve, own_vn) = self.velocity()?;
+        let (target_ve, target_vn) = target.velocity()?;
+        let (pe, pn) = self.offset_to(target);
+        let (ve, vn) = (target_ve - own_ve, target_vn - own_vn);
+        let v2 = ve * ve + vn * vn;
+        if v2 < f64::EPSILON {
+            return Some((pe.hypot(pn), 0.0));
+        }
+        let tcpa_hours = -(pe * ve + pn * vn) / v2;
+        let cpa = (pe + ve * tcpa_hours).hypot(pn + vn * tcpa_hours);
+        Some((cpa, tcpa_hours * 60.0))
+    }
+
+    /// Return the position of the target relative to this one as east and north offsets in
+    /// nautical miles using a local flat earth approximation.
+    fn offset_to(&self, target: &ShipMotion) -> (f64, f64) {
+        let mut dlon = target.longitude - self.longitude;
+        if dlon > 180.0 {
+            dlon -= 360.0;
+        } else if dlon < -180.0 {
+            dlon += 360.0;
+        }
+        let mean_lat = ((self.latitude + target.latitude) / 2.0).to_radians();
+        (
+            dlon * 60.0 * mean_lat.cos(),
+            (target.latitude - self.latitude) * 60.0,
+        )
+    }
+}
+
+impl LatLon for ShipMotion {
+    fn latitude(&self) -> Option<f64> {
+        Some(self.latitude)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        Some(self.longitude)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Alarm thresholds used by `CollisionMonitor`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CpaAlarmLimits {
+    /// Alarm is raised when CPA is at most this distance (nautical miles)
+    pub cpa_nm: f64,
+
+    /// Alarm is raised only when CPA is reached within this time (minutes)
+    pub tcpa_minutes: f64,
+}
+
+impl Default for CpaAlarmLimits {
+    fn default() -> CpaAlarmLimits {
+        CpaAlarmLimits {
+            cpa_nm: 0.5,
+            tcpa_minutes: 15.0,
+        }
+    }
+}
+
+/// Collision risk of a single target computed by `CollisionMonitor`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CpaData {
+    /// MMSI of the target vessel
+    pub mmsi: u32,
+
+    /// Current distance to the target (nautical miles)
+    pub range_nm: f64,
+
+    /// Closest point of approach (nautical miles), `None` if the speed or course of own ship
+    /// or target is not available
+    pub cpa_nm: Option<f64>,
+
+    /// Time to closest point of approach (minutes), negative if already passed. `None` if the
+    /// speed or course of own ship or target is not available.
+    pub tcpa_minutes: Option<f64>,
+
+    /// True if the target violates the alarm limits. When CPA can't be computed the current
+    /// range is compared against the CPA limit instead.
+    pub alarm: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Computes CPA/TCPA between own ship and AIS targets. Own ship position is taken from
+/// `!AIVDO` dynamic data or GNSS RMC data given to `update_own_ship`.
+#[derive(Clone, Debug, Default)]
+pub struct CollisionMonitor {
+    own_ship: Option<ShipMotion>,
+    own_mmsi: Option<u32>,
+    limits: CpaAlarmLimits,
+}
+
+impl CollisionMonitor {
+    /// Construct a monitor without own ship position using default alarm limits.
+    pub fn new() -> CollisionMonitor {
+        CollisionMonitor::default()
+    }
+
+    /// Set alarm limits.
+    pub fn set_limits(&mut self, limits: CpaAlarmLimits) {
+        self.limits = limits;
+    }
+
+    /// Return alarm limits.
+    pub fn limits(&self) -> CpaAlarmLimits {
+        self.limits
+    }
+
+    /// Set own ship position and motion directly.
+    pub fn set_own_ship(&mut self, own_ship: ShipMotion) {
+        self.own_ship = Some(own_ship);
+    }
+
+    /// Return own ship position and motion if known.
+    pub fn own_ship(&self) -> Option<ShipMotion> {
+        self.own_ship
+    }
+
+    /// Update own ship from the given message. Only own vessel dynamic data (`!AIVDO`) and RMC
+    /// messages with a valid position fix are used. Returns true if own ship was updated.
+    pub fn update_own_ship(&mut self, message: &ParsedMessage) -> bool {
+        let motion = match message {
+            ParsedMessage::VesselDynamicData(vdd) if vdd.own_vessel => {
+                self.own_mmsi = Some(vdd.mmsi);
+                ShipMotion::from_dynamic_data(vdd)
+            }
+            ParsedMessage::Rmc(rmc) => ShipMotion::from_rmc(rmc),
+            _ => None,
+        };
+        if let Some(motion) = motion {
+            self.own_ship = Some(motion);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Compute collision risk of the given target. Returns `None` if own ship or target
+    /// position is not available or the target is own vessel.
+    pub fn compute(&self, target: &VesselDynamicData) -> Option<CpaData> {
+        if target.own_vessel || Some(target.mmsi) == self.own_mmsi {
+            return None;
+        }
+        let own_ship = self.own_ship?;
+        let target_motion = ShipMotion::from_dynamic_data(target)?;
+        let (pe, pn) = own_ship.offset_to(&target_motion);
+        let range_nm = pe.hypot(pn);
+        let cpa_tcpa = own_ship.cpa(&target_motion);
+        let alarm = match cpa_tcpa {
+            Some((cpa, tcpa)) => {
+                range_nm <= self.limits.cpa_nm
+                    || (cpa <= self.limits.cpa_nm
+                        && tcpa >= 0.0
+                        && tcpa <= self.limits.tcpa_minutes)
+            }
+            None => range_nm <= self.limits.cpa_nm,
+        };
+        Some(CpaData {
+            mmsi: target.mmsi,
+            range_nm,
+            cpa_nm: cpa_tcpa.map(|(cpa, _)| cpa),
+            tcpa_minutes: cpa_tcpa.map(|(_, tcpa)| tcpa),
+            alarm,
+        })
+    }
+
+    /// Compute collision risk of every vessel in the tracker having a known position. The
+    /// result is ordered by the current range.
+    pub fn compute_all(&self, tracker: &VesselTracker) -> Vec<CpaData> {
+        let mut result: Vec<CpaData> = tracker
+            .iter()
+            .filter_map(|v| v.dynamic_data.as_ref())
+            .filter_map(|vdd| self.compute(vdd))
+            .collect();
+        result.sort_by(|a, b| {
+            a.range_nm
+                .partial_cmp(&b.range_nm)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        result
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn target(
+        mmsi: u32,
+        lat: f64,
+        lon: f64,
+        sog: Option<f64>,
+        cog: Option<f64>,
+    ) -> VesselDynamicData {
+        VesselDynamicData {
+            mmsi,
+            latitude: Some(lat),
+            longitude: Some(lon),
+            sog_knots: sog,
+            cog,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cpa_head_on() {
+        let own = ShipMotion {
+            latitude: 60.0,
+            longitude: 25.0,
+            sog_knots: Some(10.0),
+            cog: Some(0.0),
+        };
+        // Target two miles ahead on reciprocal course
+        let other = ShipMotion {
+            latitude: 60.0 + 2.0 / 60.0,
+            longitude: 25.0,
+            sog_knots: Some(10.0),
+            cog: Some(180.0),
+        };
+        let (cpa, tcpa) = own.cpa(&other).unwrap();
+        assert::close(cpa, 0.0, 0.001);
+        assert::close(tcpa, 6.0, 0.001);
+
+        // Target one mile abeam to starboard overtaken from behind
+        let other = ShipMotion {
+            latitude: 60.0 - 1.0 / 60.0,
+            longitude: 25.0 + 1.0 / 30.0,
+            sog_knots: Some(5.0),
+            cog: Some(0.0),
+        };
+        let (cpa, tcpa) = own.cpa(&other).unwrap();
+        assert::close(cpa, 1.0, 0.01);
+        assert::close(tcpa, -12.0, 0.1);
+
+        // Equal velocities
+        let other = ShipMotion {
+            latitude: 60.0,
+            longitude: 25.0 + 1.0 / 30.0,
+            ..own
+        };
+        let (cpa, tcpa) = own.cpa(&other).unwrap();
+        assert::close(cpa, 1.0, 0.01);
+        assert::close(tcpa, 0.0, 0.001);
+    }
+
+    #[test]
+    fn test_collision_monitor() {
+        let mut p = NmeaParser::new();
+        let mut monitor = CollisionMonitor::new();
+        let mut tracker = VesselTracker::new();
+
+        assert_eq!(monitor.compute(&target(1, 49.3, -123.2, None, None)), None);
+
+        // Void fix is ignored
+        let rmc = p
+            .parse_sentence("$GPRMC,225446,V,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*70")
+            .unwrap();
+        assert!(!monitor.update_own_ship(&rmc));
+        assert_eq!(monitor.own_ship(), None);
+
+        let rmc = p
+            .parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67")
+            .unwrap();
+        assert!(monitor.update_own_ship(&rmc));
+        let own = monitor.own_ship().unwrap();
+        assert_eq!(own.sog_knots, Some(0.5));
+
+        // Approaching from the north at 12 knots, 3 miles away
+        let t1 = target(
+            1,
+            own.latitude + 3.0 / 60.0,
+            own.longitude,
+            Some(12.0),
+            Some(180.0),
+        );
+        // Anchored 0.3 miles away without course
+        let t2 = target(2, own.latitude + 0.3 / 60.0, own.longitude, Some(0.0), None);
+        // Speed not available
+        let t3 = target(
+            3,
+            own.latitude - 5.0 / 60.0,
+            own.longitude,
+            None,
+            Some(90.0),
+        );
+        // Own vessel
+        let mut t4 = target(4, own.latitude, own.longitude, Some(0.0), None);
+        t4.own_vessel = true;
+        for vdd in [t1, t2, t3, t4].iter() {
+            tracker.update(&ParsedMessage::VesselDynamicData(vdd.clone()));
+        }
+
+        let result = monitor.compute_all(&tracker);
+        assert_eq!(
+            result.iter().map(|c| c.mmsi).collect::<Vec<u32>>(),
+            vec![2, 1, 3]
+        );
+
+        assert!(result[0].alarm);
+        assert!(result[0].cpa_nm.is_some());
+
+        assert!(result[1].alarm);
+        assert::close(result[1].range_nm, 3.0, 0.001);
+        assert::close(result[1].cpa_nm.unwrap(), 0.1, 0.01);
+        assert::close(result[1].tcpa_minutes.unwrap(), 14.6, 0.1);
+
+        assert!(!result[2].alarm);
+        assert_eq!(result[2].cpa_nm, None);
+        assert_eq!(result[2].tcpa_minutes, None);
+
+        // Stricter limits
+        monitor.set_limits(CpaAlarmLimits {
+            cpa_nm: 0.2,
+            tcpa_minutes: 10.0,
+        });
+        let result = monitor.compute_all(&tracker);
+        assert!(result.iter().all(|c| !c.alarm));
+    }
+}
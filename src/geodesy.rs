@@ -0,0 +1,290 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Geodetic computations used by the provided methods of `LatLon`
+
+// -------------------------------------------------------------------------------------------------
+
+/// Mean earth radius (metres)
+pub(crate) const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+/// Length of a nautical mile (metres)
+pub(crate) const NAUTICAL_MILE_M: f64 = 1852.0;
+
+/// WGS84 semi-major axis (metres)
+const WGS84_A: f64 = 6_378_137.0;
+
+/// WGS84 flattening
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Maximum number of Vincenty iterations before giving up
+const VINCENTY_MAX_ITERATIONS: usize = 200;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Normalize the given angle to range [0, 360).
+fn normalize_bearing(deg: f64) -> f64 {
+    let b = deg % 360.0;
+    if b < 0.0 {
+        b + 360.0
+    } else {
+        b
+    }
+}
+
+/// Normalize the given longitude to range [-180, 180).
+fn normalize_longitude(deg: f64) -> f64 {
+    normalize_bearing(deg + 180.0) - 180.0
+}
+
+/// Great-circle distance on a spherical earth using the haversine formula (metres).
+pub(crate) fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = phi2 - phi1;
+    let dlambda = (lon2 - lon1).to_radians();
+    let a = (dphi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (dlambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+}
+
+/// Initial great-circle bearing from point 1 to point 2 (degrees).
+pub(crate) fn initial_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dlambda = (lon2 - lon1).to_radians();
+    let y = dlambda.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * dlambda.cos();
+    normalize_bearing(y.atan2(x).to_degrees())
+}
+
+/// Great-circle bearing at point 2 when arriving from point 1 (degrees).
+pub(crate) fn final_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    normalize_bearing(initial_bearing(lat2, lon2, lat1, lon1) + 180.0)
+}
+
+/// Destination point on a spherical earth given start point, initial bearing (degrees) and
+/// distance (metres). Returns latitude and longitude in degrees.
+pub(crate) fn destination_point(lat: f64, lon: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let phi1 = lat.to_radians();
+    let lambda1 = lon.to_radians();
+    let theta = bearing.to_radians();
+    let delta = distance / EARTH_RADIUS_M;
+    let phi2 = (phi1.sin() * delta.cos() + phi1.cos() * delta.sin() * theta.cos()).asin();
+    let lambda2 = lambda1
+        + (theta.sin() * delta.sin() * phi1.cos()).atan2(delta.cos() - phi1.sin() * phi2.sin());
+    (phi2.to_degrees(), normalize_longitude(lambda2.to_degrees()))
+}
+
+/// Return latitude difference and projected latitude difference used in rhumb line formulas.
+fn rhumb_deltas(lat1: f64, lat2: f64) -> (f64, f64) {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = phi2 - phi1;
+    let dpsi = ((phi2 / 2.0 + std::f64::consts::FRAC_PI_4).tan()
+        / (phi1 / 2.0 + std::f64::consts::FRAC_PI_4).tan())
+    .ln();
+    (dphi, dpsi)
+}
+
+/// Return longitude difference along the shorter way around (radians).
+fn shortest_dlambda(lon1: f64, lon2: f64) -> f64 {
+    normalize_longitude(lon2 - lon1).to_radians()
+}
+
+/// Rhumb line distance on a spherical earth (metres).
+pub(crate) fn rhumb_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (dphi, dpsi) = rhumb_deltas(lat1, lat2);
+    let dlambda = shortest_dlambda(lon1, lon2);
+    let q = if dpsi.abs() > 1e-12 {
+        dphi / dpsi
+    } else {
+        lat1.to_radians().cos()
+    };
+    (dphi * dphi + q * q * dlambda * dlambda).sqrt() * EARTH_RADIUS_M
+}
+
+/// Constant rhumb line bearing from point 1 to point 2 (degrees).
+pub(crate) fn rhumb_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (_, dpsi) = rhumb_deltas(lat1, lat2);
+    let dlambda = shortest_dlambda(lon1, lon2);
+    normalize_bearing(dlambda.atan2(dpsi).to_degrees())
+}
+
+/// Distance on the WGS84 ellipsoid using Vincenty's inverse formula (metres). Returns `None`
+/// if the iteration doesn't converge, which may happen for nearly antipodal points.
+pub(crate) fn vincenty_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Option<f64> {
+    let b = WGS84_A * (1.0 - WGS84_F);
+    let l = shortest_dlambda(lon1, lon2);
+    let u1 = ((1.0 - WGS84_F) * lat1.to_radians().tan()).atan();
+    let u2 = ((1.0 - WGS84_F) * lat2.to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    for _ in 0..VINCENTY_MAX_ITERATIONS {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            // Coincident points
+            return Some(0.0);
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+        let cos_2sigma_m = if cos2_alpha != 0.0 {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        } else {
+            // Equatorial line
+            0.0
+        };
+        let c = WGS84_F / 16.0 * cos2_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos2_alpha));
+        let lambda_prev = lambda;
+        lambda = l
+            + (1.0 - c)
+                * WGS84_F
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+        if (lambda - lambda_prev).abs() < 1e-12 {
+            let u_sq = cos2_alpha * (WGS84_A * WGS84_A - b * b) / (b * b);
+            let k1 = ((1.0 + u_sq).sqrt() - 1.0) / ((1.0 + u_sq).sqrt() + 1.0);
+            let aa = (1.0 + k1 * k1 / 4.0) / (1.0 - k1);
+            let bb = k1 * (1.0 - 3.0 / 8.0 * k1 * k1);
+            let delta_sigma = bb
+                * sin_sigma
+                * (cos_2sigma_m
+                    + bb / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                            - bb / 6.0
+                                * cos_2sigma_m
+                                * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+            return Some(b * aa * (sigma - delta_sigma));
+        }
+    }
+    None
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn test_great_circle() {
+        let lands_end = (
+            50.0 + 3.0 / 60.0 + 59.0 / 3600.0,
+            -(5.0 + 42.0 / 60.0 + 53.0 / 3600.0),
+        );
+        let john_o_groats = (
+            58.0 + 38.0 / 60.0 + 38.0 / 3600.0,
+            -(3.0 + 4.0 / 60.0 + 12.0 / 3600.0),
+        );
+        assert::close(
+            lands_end.distance_m(&john_o_groats).unwrap(),
+            968_900.0,
+            100.0,
+        );
+        assert::close(lands_end.distance_nm(&john_o_groats).unwrap(), 523.2, 0.1);
+        assert::close(
+            lands_end.initial_bearing(&john_o_groats).unwrap(),
+            9.1198,
+            0.001,
+        );
+        assert::close(
+            lands_end.final_bearing(&john_o_groats).unwrap(),
+            11.2752,
+            0.001,
+        );
+        assert_eq!(lands_end.distance_m(&(0.0, 0.0)).map(|_| ()), Some(()));
+
+        let (lat, lon) = (53.3206, -1.7297)
+            .destination_m(96.0217, 124_800.0)
+            .unwrap();
+        assert::close(lat, 53.1883, 0.001);
+        assert::close(lon, 0.1333, 0.001);
+        let (lat, lon) = (0.0, 179.5).destination_nm(90.0, 60.0).unwrap();
+        assert::close(lat, 0.0, 0.0001);
+        assert::close(lon, -179.5, 0.01);
+    }
+
+    #[test]
+    fn test_rhumb_line() {
+        let dover = (
+            51.0 + 7.0 / 60.0 + 32.0 / 3600.0,
+            1.0 + 20.0 / 60.0 + 17.0 / 3600.0,
+        );
+        let calais = (
+            50.0 + 57.0 / 60.0 + 48.0 / 3600.0,
+            1.0 + 51.0 / 60.0 + 9.0 / 3600.0,
+        );
+        assert::close(dover.rhumb_distance_m(&calais).unwrap(), 40_235.0, 10.0);
+        assert::close(dover.rhumb_bearing(&calais).unwrap(), 116.636, 0.01);
+
+        // Along a meridian
+        assert::close(
+            (0.0, 0.0).rhumb_distance_nm(&(1.0, 0.0)).unwrap(),
+            60.04,
+            0.01,
+        );
+        assert::close(
+            (0.0, 0.0).rhumb_bearing(&(-1.0, 0.0)).unwrap(),
+            180.0,
+            0.0001,
+        );
+    }
+
+    #[test]
+    fn test_vincenty() {
+        let flinders_peak = (
+            -(37.0 + 57.0 / 60.0 + 3.72030 / 3600.0),
+            144.0 + 25.0 / 60.0 + 29.52440 / 3600.0,
+        );
+        let buninyong = (
+            -(37.0 + 39.0 / 60.0 + 10.15610 / 3600.0),
+            143.0 + 55.0 / 60.0 + 35.38390 / 3600.0,
+        );
+        assert::close(
+            flinders_peak.ellipsoidal_distance_m(&buninyong).unwrap(),
+            54_972.271,
+            0.001,
+        );
+        assert::close(
+            flinders_peak.ellipsoidal_distance_nm(&buninyong).unwrap(),
+            29.6827,
+            0.0001,
+        );
+        assert_eq!(
+            flinders_peak.ellipsoidal_distance_m(&flinders_peak),
+            Some(0.0)
+        );
+
+        // Equator
+        assert::close(
+            (0.0, 0.0).ellipsoidal_distance_m(&(0.0, 1.0)).unwrap(),
+            111_319.491,
+            0.001,
+        );
+
+        // Missing position
+        let rmc = ais::VesselDynamicData::default();
+        assert_eq!(rmc.ellipsoidal_distance_m(&buninyong), None);
+        assert_eq!(buninyong.distance_m(&rmc), None);
+        assert_eq!(rmc.destination_m(0.0, 1.0), None);
+    }
+}
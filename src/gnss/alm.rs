@@ -18,6 +18,7 @@ use super::*;
 
 /// ALM - GPS Almanac Data
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlmData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -18,6 +18,7 @@ use super::*;
 
 /// DTM - Datum being used
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DtmData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -0,0 +1,365 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Consolidated GNSS fix combined from the sentences of one epoch
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Satellite counts of a single navigation system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SatelliteCounts {
+    /// Navigation system (talker of GSA and GSV sentences)
+    pub source: NavigationSystem,
+
+    /// Number of satellites used in the solution (GSA)
+    pub used: usize,
+
+    /// Number of satellites in view (GSV)
+    pub in_view: usize,
+}
+
+/// GNSS fix consolidated from the GGA, RMC, GSA, GSV, VTG, GNS and ZDA sentences of one epoch.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GnssFix {
+    /// UTC date and time of the fix. The date is valid only if `date_valid` is true.
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// True if the date of `timestamp` is known from RMC or ZDA sentence of this or an earlier
+    /// epoch
+    pub date_valid: bool,
+
+    /// Latitude in degrees
+    pub latitude: Option<f64>,
+
+    /// Longitude in degrees
+    pub longitude: Option<f64>,
+
+    /// Altitude above mean sea level (metres)
+    pub altitude: Option<f64>,
+
+    /// Height of geoid above WGS84 ellipsoid (metres)
+    pub geoid_separation: Option<f64>,
+
+    /// Speed over ground in knots
+    pub sog_knots: Option<f64>,
+
+    /// Course over ground in degrees, relative to true north
+    pub cog: Option<f64>,
+
+    /// Fix quality (GGA)
+    pub quality: Option<GgaQualityIndicator>,
+
+    /// Fix type (GSA)
+    pub fix_mode: Option<GsaFixMode>,
+
+    /// Status of the fix (RMC), true = active, false = void
+    pub status_active: Option<bool>,
+
+    /// Number of satellites used in the solution as reported by GGA or GNS
+    pub satellite_count: Option<u8>,
+
+    /// Position (3D) dilution of precision
+    pub pdop: Option<f64>,
+
+    /// Horizontal dilution of precision
+    pub hdop: Option<f64>,
+
+    /// Vertical dilution of precision
+    pub vdop: Option<f64>,
+
+    /// Satellites used and in view per navigation system
+    pub satellites: Vec<SatelliteCounts>,
+}
+
+impl GnssFix {
+    /// Return true if no data has been collected to the fix.
+    pub fn is_empty(&self) -> bool {
+        *self == GnssFix::default()
+    }
+
+    /// Return the satellite counts entry of the given navigation system, creating it if needed.
+    fn satellites_mut(&mut self, source: NavigationSystem) -> &mut SatelliteCounts {
+        let i = match self.satellites.iter().position(|s| s.source == source) {
+            Some(i) => i,
+            None => {
+                self.satellites.push(SatelliteCounts {
+                    source,
+                    used: 0,
+                    in_view: 0,
+                });
+                self.satellites.len() - 1
+            }
+        };
+        &mut self.satellites[i]
+    }
+
+    /// Set the time of the fix unless a time with a valid date has already been set.
+    fn set_timestamp(&mut self, timestamp: Option<DateTime<Utc>>, date_valid: bool) {
+        if timestamp.is_some() && (date_valid || !self.date_valid) {
+            self.timestamp = timestamp;
+            self.date_valid = date_valid;
+        }
+    }
+}
+
+impl LatLon for GnssFix {
+    fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Aggregator combining GNSS sentences into one `GnssFix` per epoch. A new epoch starts when a
+/// GGA, RMC, GNS or ZDA sentence carrying a time of day different from the current epoch is
+/// received. GSA, GSV and VTG sentences don't carry time and are added to the current epoch.
+#[derive(Clone, Debug, Default)]
+pub struct GnssFixState {
+    current: GnssFix,
+    epoch_time: Option<chrono::NaiveTime>,
+    last_dated: Option<DateTime<Utc>>,
+}
+
+impl GnssFixState {
+    /// Construct an empty state.
+    pub fn new() -> GnssFixState {
+        GnssFixState::default()
+    }
+
+    /// Update the state with the given message. When the message starts a new epoch the fix
+    /// of the previous epoch is returned. Messages other than GGA, RMC, GSA, GSV, VTG, GNS and
+    /// ZDA are ignored.
+    pub fn update(&mut self, message: &ParsedMessage) -> Option<GnssFix> {
+        let completed = match message {
+            ParsedMessage::Gga(gga) => self.start_epoch(gga.timestamp),
+            ParsedMessage::Rmc(rmc) => self.start_epoch(rmc.timestamp),
+            ParsedMessage::Gns(gns) => self.start_epoch(gns.timestamp),
+            ParsedMessage::Zda(zda) => self.start_epoch(zda.timestamp_utc),
+            ParsedMessage::Gsa(_) | ParsedMessage::Gsv(_) | ParsedMessage::Vtg(_) => None,
+            _ => return None,
+        };
+
+        let fix = &mut self.current;
+        match message {
+            ParsedMessage::Gga(gga) => {
+                fix.set_timestamp(gga.timestamp, false);
+                fix.latitude = gga.latitude.or(fix.latitude);
+                fix.longitude = gga.longitude.or(fix.longitude);
+                fix.altitude = gga.altitude;
+                fix.geoid_separation = gga.geoid_separation;
+                fix.quality = Some(gga.quality);
+                fix.satellite_count = gga.satellite_count;
+                fix.hdop = gga.hdop.or(fix.hdop);
+            }
+            ParsedMessage::Rmc(rmc) => {
+                fix.set_timestamp(rmc.timestamp, true);
+                fix.latitude = rmc.latitude.or(fix.latitude);
+                fix.longitude = rmc.longitude.or(fix.longitude);
+                fix.sog_knots = rmc.sog_knots.or(fix.sog_knots);
+                fix.cog = rmc.bearing.or(fix.cog);
+                fix.status_active = rmc.status_active;
+            }
+            ParsedMessage::Gns(gns) => {
+                fix.set_timestamp(gns.timestamp, false);
+                fix.latitude = gns.latitude.or(fix.latitude);
+                fix.longitude = gns.longitude.or(fix.longitude);
+                fix.altitude = gns.altitude.or(fix.altitude);
+                fix.geoid_separation = gns.geoid_separation.or(fix.geoid_separation);
+                fix.satellite_count = gns.satellite_count.or(fix.satellite_count);
+                fix.hdop = gns.hdop.or(fix.hdop);
+            }
+            ParsedMessage::Zda(zda) => {
+                fix.set_timestamp(zda.timestamp_utc, true);
+            }
+            ParsedMessage::Gsa(gsa) => {
+                fix.fix_mode = gsa.mode2_3d.or(fix.fix_mode);
+                fix.pdop = gsa.pdop.or(fix.pdop);
+                fix.hdop = gsa.hdop.or(fix.hdop);
+                fix.vdop = gsa.vdop.or(fix.vdop);
+                fix.satellites_mut(gsa.source).used += gsa.prn_numbers.len();
+            }
+            ParsedMessage::Gsv(gsv) => {
+                if let Some(first) = gsv.first() {
+                    fix.satellites_mut(first.source).in_view = gsv.len();
+                }
+            }
+            ParsedMessage::Vtg(vtg) => {
+                fix.sog_knots = vtg.sog_knots.or(fix.sog_knots);
+                fix.cog = vtg.cog_true.or(fix.cog);
+            }
+            _ => {}
+        }
+        completed
+    }
+
+    /// Return the fix of the current, possibly incomplete, epoch.
+    pub fn current(&self) -> &GnssFix {
+        &self.current
+    }
+
+    /// Complete the current epoch and return its fix, or `None` if no data has been collected.
+    pub fn flush(&mut self) -> Option<GnssFix> {
+        self.epoch_time = None;
+        self.take_current()
+    }
+
+    /// Start a new epoch if the given time differs from the time of the current epoch.
+    /// Returns the fix of the completed epoch.
+    fn start_epoch(&mut self, timestamp: Option<DateTime<Utc>>) -> Option<GnssFix> {
+        let time = timestamp?.time();
+        if self.epoch_time == Some(time) {
+            return None;
+        }
+        let completed = if self.epoch_time.is_some() {
+            self.take_current()
+        } else {
+            None
+        };
+        self.epoch_time = Some(time);
+        completed
+    }
+
+    /// Take the current fix replacing it with an empty one. The date of a fix without RMC or
+    /// ZDA sentence is taken from the previous epochs.
+    fn take_current(&mut self) -> Option<GnssFix> {
+        let mut fix = std::mem::take(&mut self.current);
+        if fix.is_empty() {
+            return None;
+        }
+        if !fix.date_valid {
+            if let (Some(last), Some(timestamp)) = (self.last_dated, fix.timestamp) {
+                let mut dated = parse_valid_utc(
+                    last.year(),
+                    last.month(),
+                    last.day(),
+                    timestamp.hour(),
+                    timestamp.minute(),
+                    timestamp.second(),
+                    timestamp.nanosecond(),
+                )
+                .unwrap_or(timestamp);
+                if dated < last {
+                    // Midnight passed since the last date was received
+                    dated += chrono::Duration::days(1);
+                }
+                fix.timestamp = Some(dated);
+                fix.date_valid = true;
+            }
+        }
+        if fix.date_valid {
+            self.last_dated = fix.timestamp;
+        }
+        Some(fix)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gnss_fix_state() {
+        let mut p = NmeaParser::new();
+        let mut state = GnssFixState::new();
+        let mut fixes = Vec::new();
+        for s in [
+            "$GPRMC,235959.00,A,6010.4856,N,02450.9910,E,5.2,123.4,311220,,,A*55",
+            "$GPGGA,235959.00,6010.4856,N,02450.9910,E,1,08,0.9,12.3,M,18.0,M,,*5F",
+            "$GPGSA,A,3,01,03,06,11,14,17,,,,,,,1.8,0.9,1.5*31",
+            "$GLGSA,A,3,65,72,,,,,,,,,,,1.8,0.9,1.5*2C",
+            "$GPGSV,2,1,07,01,40,083,46,03,60,150,44,06,12,300,30,11,34,212,39*79",
+            "$GPGSV,2,2,07,14,50,040,41,17,22,270,33,19,05,010,*40",
+            "$GPVTG,123.4,T,,M,5.2,N,9.6,K,A*01",
+            "$GPGGA,000000.00,6010.4800,N,02451.0000,E,2,09,0.8,12.0,M,18.0,M,,*5D",
+            "$GPVTG,125.0,T,,M,5.3,N,9.8,K,A*0C",
+            "$GPGGA,000001.00,6010.4700,N,02451.0100,E,2,09,0.8,12.0,M,18.0,M,,*52",
+        ]
+        .iter()
+        {
+            match p.parse_sentence(s) {
+                Ok(msg) => {
+                    if let Some(fix) = state.update(&msg) {
+                        fixes.push(fix);
+                    }
+                }
+                Err(e) => assert_eq!(e.to_string(), "OK"),
+            }
+        }
+        assert_eq!(fixes.len(), 2);
+        assert!(!state.current().is_empty());
+        fixes.push(state.flush().unwrap());
+        assert_eq!(state.flush(), None);
+
+        let fix = &fixes[0];
+        assert_eq!(
+            fix.timestamp,
+            Some(Utc.ymd(2020, 12, 31).and_hms(23, 59, 59))
+        );
+        assert!(fix.date_valid);
+        assert::close(fix.latitude.unwrap_or(0.0), 60.174760, 0.000001);
+        assert::close(fix.longitude.unwrap_or(0.0), 24.849850, 0.000001);
+        assert_eq!(fix.altitude, Some(12.3));
+        assert_eq!(fix.geoid_separation, Some(18.0));
+        assert_eq!(fix.sog_knots, Some(5.2));
+        assert_eq!(fix.cog, Some(123.4));
+        assert_eq!(fix.quality, Some(GgaQualityIndicator::GpsFix));
+        assert_eq!(fix.fix_mode, Some(GsaFixMode::Fix3D));
+        assert_eq!(fix.status_active, Some(true));
+        assert_eq!(fix.satellite_count, Some(8));
+        assert_eq!(fix.pdop, Some(1.8));
+        assert_eq!(fix.hdop, Some(0.9));
+        assert_eq!(fix.vdop, Some(1.5));
+        assert_eq!(
+            fix.satellites,
+            vec![
+                SatelliteCounts {
+                    source: NavigationSystem::Gps,
+                    used: 6,
+                    in_view: 7,
+                },
+                SatelliteCounts {
+                    source: NavigationSystem::Glonass,
+                    used: 2,
+                    in_view: 0,
+                },
+            ]
+        );
+
+        // Date carried over midnight from the RMC of the previous epoch
+        let fix = &fixes[1];
+        assert_eq!(fix.timestamp, Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)));
+        assert!(fix.date_valid);
+        assert_eq!(fix.quality, Some(GgaQualityIndicator::DGpsFix));
+        assert_eq!(fix.sog_knots, Some(5.3));
+        assert_eq!(fix.cog, Some(125.0));
+        assert_eq!(fix.status_active, None);
+        assert_eq!(fix.satellites, vec![]);
+
+        let fix = &fixes[2];
+        assert_eq!(fix.timestamp, Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 1)));
+        assert_eq!(fix.sog_knots, None);
+    }
+}
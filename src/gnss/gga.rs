@@ -18,6 +18,7 @@ use super::*;
 
 /// GGA - time, position, and fix related data
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GgaData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -65,6 +66,7 @@ impl LatLon for GgaData {
 
 /// GGA GPS quality indicator
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GgaQualityIndicator {
     Invalid,                // 0
     GpsFix,                 // 1
@@ -92,6 +94,10 @@ impl GgaQualityIndicator {
             _ => GgaQualityIndicator::Invalid,
         }
     }
+
+    pub fn to_value(&self) -> u8 {
+        *self as u8
+    }
 }
 
 impl std::fmt::Display for GgaQualityIndicator {
@@ -118,13 +124,13 @@ impl std::fmt::Display for GgaQualityIndicator {
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut NmeaParser,
 ) -> Result<ParsedMessage, ParseError> {
-    let now: DateTime<Utc> = Utc::now();
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Gga(GgaData {
         source: nav_system,
-        timestamp: parse_hhmmss(split.get(1).unwrap_or(&""), now).ok(),
+        timestamp: store.resolve_time_of_day(split.get(1).unwrap_or(&"")).ok(),
         latitude: parse_latitude_ddmm_mmm(
             split.get(2).unwrap_or(&""),
             split.get(3).unwrap_or(&""),
@@ -143,6 +149,32 @@ pub(crate) fn handle(
     }))
 }
 
+impl GgaData {
+    /// Format the data as xxGGA sentence with the given talker ID (e.g. "GP").
+    pub fn to_sentence(&self, talker_id: &str) -> String {
+        append_checksum(&format!(
+            "${}GGA,{},{},{},{},{},{},{},{},{},{},{},{}",
+            talker_id,
+            format_hhmmss_ss(self.timestamp),
+            format_latitude_ddmm_mmmm(self.latitude),
+            format_longitude_dddmm_mmmm(self.longitude),
+            self.quality.to_value(),
+            format_padded_field(self.satellite_count, 2),
+            format_f64_field(self.hdop, 1),
+            format_f64_field(self.altitude, 1),
+            if self.altitude.is_some() { "M" } else { "" },
+            format_f64_field(self.geoid_separation, 1),
+            if self.geoid_separation.is_some() {
+                "M"
+            } else {
+                ""
+            },
+            format_f64_field(self.age_of_dgps, 1),
+            format_padded_field(self.ref_station_id, 4),
+        ))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -153,19 +185,14 @@ mod test {
     fn test_parse_cpgga() {
         // General test
         let mut p = NmeaParser::new();
+        p.set_reference_time(Some(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
         match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
         {
             Ok(ps) => {
                 match ps {
                     // The expected result
                     ParsedMessage::Gga(gga) => {
-                        assert_eq!(gga.timestamp, {
-                            let now: DateTime<Utc> = Utc::now();
-                            Some(
-                                Utc.ymd(now.year(), now.month(), now.day())
-                                    .and_hms(12, 35, 19),
-                            )
-                        });
+                        assert_eq!(gga.timestamp, Some(Utc.ymd(2021, 5, 1).and_hms(12, 35, 19)));
                         assert::close(gga.latitude.unwrap_or(0.0), 48.117, 0.001);
                         assert::close(gga.longitude.unwrap_or(0.0), 11.517, 0.001);
                         assert_eq!(gga.quality, GgaQualityIndicator::GpsFix);
@@ -220,18 +247,13 @@ mod test {
 
         // Empty fields test
         let mut p = NmeaParser::new();
+        p.set_reference_time(Some(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
         match p.parse_sentence("$GPGGA,123519,,,,,,,,,,,,,*5B") {
             Ok(ps) => {
                 match ps {
                     // The expected result
                     ParsedMessage::Gga(gga) => {
-                        assert_eq!(gga.timestamp, {
-                            let now: DateTime<Utc> = Utc::now();
-                            Some(
-                                Utc.ymd(now.year(), now.month(), now.day())
-                                    .and_hms(12, 35, 19),
-                            )
-                        });
+                        assert_eq!(gga.timestamp, Some(Utc.ymd(2021, 5, 1).and_hms(12, 35, 19)));
                         assert_eq!(gga.latitude, None);
                         assert_eq!(gga.longitude, None);
                         assert_eq!(gga.quality, GgaQualityIndicator::Invalid);
@@ -255,4 +277,22 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_gga_to_sentence() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                let sentence = gga.to_sentence("GP");
+                assert_eq!(
+                    sentence,
+                    "$GPGGA,123519.00,4807.0380,N,01131.0000,E,1,08,0.9,545.4,M,46.9,M,,*69"
+                );
+                assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Gga(gga)));
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
 }
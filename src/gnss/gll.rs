@@ -17,6 +17,7 @@ use super::*;
 
 /// GLL - geographic Position - Latitude/Longitude
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GllData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -53,8 +54,8 @@ impl LatLon for GllData {
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut NmeaParser,
 ) -> Result<ParsedMessage, ParseError> {
-    let now: DateTime<Utc> = Utc::now();
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Gll(GllData {
@@ -67,7 +68,7 @@ pub(crate) fn handle(
             split.get(3).unwrap_or(&""),
             split.get(4).unwrap_or(&""),
         )?,
-        timestamp: parse_hhmmss(split.get(5).unwrap_or(&""), now).ok(),
+        timestamp: store.resolve_time_of_day(split.get(5).unwrap_or(&"")).ok(),
         data_valid: {
             match *split.get(6).unwrap_or(&"") {
                 "A" => Some(true),
@@ -79,6 +80,25 @@ pub(crate) fn handle(
     }))
 }
 
+impl GllData {
+    /// Format the data as xxGLL sentence with the given talker ID (e.g. "GP").
+    pub fn to_sentence(&self, talker_id: &str) -> String {
+        append_checksum(&format!(
+            "${}GLL,{},{},{},{},{}",
+            talker_id,
+            format_latitude_ddmm_mmmm(self.latitude),
+            format_longitude_dddmm_mmmm(self.longitude),
+            format_hhmmss_ss(self.timestamp),
+            match self.data_valid {
+                Some(true) => "A",
+                Some(false) => "V",
+                None => "",
+            },
+            self.faa_mode.map(|m| m.to_string()).unwrap_or_default(),
+        ))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -88,6 +108,7 @@ mod test {
     #[test]
     fn test_parse_gagll() {
         let mut p = NmeaParser::new();
+        p.set_reference_time(Some(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
         match p.parse_sentence("$GAGLL,4916.45,N,12311.12,W,225444,A,D*48") {
             Ok(ps) => {
                 match ps {
@@ -96,13 +117,7 @@ mod test {
                         assert_eq!(gll.source, NavigationSystem::Galileo);
                         assert::close(gll.latitude.unwrap_or(0.0), 49.3, 0.1);
                         assert::close(gll.longitude.unwrap_or(0.0), -123.2, 0.1);
-                        assert_eq!(gll.timestamp, {
-                            let now: DateTime<Utc> = Utc::now();
-                            Some(
-                                Utc.ymd(now.year(), now.month(), now.day())
-                                    .and_hms(22, 54, 44),
-                            )
-                        });
+                        assert_eq!(gll.timestamp, Some(Utc.ymd(2021, 5, 1).and_hms(22, 54, 44)));
                         assert_eq!(gll.data_valid, Some(true));
                         assert_eq!(gll.faa_mode, Some(FaaMode::Differential));
                     }
@@ -116,4 +131,18 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_gll_to_sentence() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GAGLL,4916.45,N,12311.12,W,225444,A,D*48") {
+            Ok(ParsedMessage::Gll(gll)) => {
+                let sentence = gll.to_sentence("GA");
+                assert_eq!(sentence, "$GAGLL,4916.4500,N,12311.1200,W,225444.00,A,D*66");
+                assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Gll(gll)));
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
 }
@@ -18,6 +18,7 @@ use super::*;
 
 /// GNS - GNSS fix data
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GnsData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -71,6 +72,7 @@ impl LatLon for GnsData {
 
 /// GNS mode indicator
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GnsModeIndicator {
     /// Satellite system not used in position fix, or fix not valid
     Invalid,
@@ -137,14 +139,14 @@ impl std::fmt::Display for GnsModeIndicator {
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &mut NmeaParser,
 ) -> Result<ParsedMessage, ParseError> {
-    let now: DateTime<Utc> = Utc::now();
     let split: Vec<&str> = sentence.split(',').collect();
     let modes: Vec<char> = split.get(6).unwrap_or(&"").chars().collect();
 
     Ok(ParsedMessage::Gns(GnsData {
         source: nav_system,
-        timestamp: parse_hhmmss(split.get(1).unwrap_or(&""), now).ok(),
+        timestamp: store.resolve_time_of_day(split.get(1).unwrap_or(&"")).ok(),
         latitude: parse_latitude_ddmm_mmm(
             split.get(2).unwrap_or(&""),
             split.get(3).unwrap_or(&""),
@@ -179,6 +181,7 @@ mod test {
     fn test_parse_cpgns() {
         // General test
         let mut p = NmeaParser::new();
+        p.set_reference_time(Some(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
         match p.parse_sentence(
             "$GNGNS,090310.00,4806.891632,N,01134.134167,E,AAN,10,1.0,532.4,47.0,,,V*68",
         ) {
@@ -186,13 +189,7 @@ mod test {
                 match ps {
                     // The expected result
                     ParsedMessage::Gns(gns) => {
-                        assert_eq!(gns.timestamp, {
-                            let now: DateTime<Utc> = Utc::now();
-                            Some(
-                                Utc.ymd(now.year(), now.month(), now.day())
-                                    .and_hms(09, 03, 10),
-                            )
-                        });
+                        assert_eq!(gns.timestamp, Some(Utc.ymd(2021, 5, 1).and_hms(09, 03, 10)));
                         assert::close(gns.latitude.unwrap_or(0.0), 48.114, 0.001);
                         assert::close(gns.longitude.unwrap_or(0.0), 11.569, 0.001);
                         assert_eq!(gns.gps_mode, GnsModeIndicator::Autonomous);
@@ -220,18 +217,13 @@ mod test {
 
         // Empty fields test
         let mut p = NmeaParser::new();
+        p.set_reference_time(Some(Utc.ymd(2021, 5, 1).and_hms(12, 0, 0)));
         match p.parse_sentence("$GPGNS,123519,,,,,,,,,,,,,*40") {
             Ok(ps) => {
                 match ps {
                     // The expected result
                     ParsedMessage::Gns(gns) => {
-                        assert_eq!(gns.timestamp, {
-                            let now: DateTime<Utc> = Utc::now();
-                            Some(
-                                Utc.ymd(now.year(), now.month(), now.day())
-                                    .and_hms(12, 35, 19),
-                            )
-                        });
+                        assert_eq!(gns.timestamp, Some(Utc.ymd(2021, 5, 1).and_hms(12, 35, 19)));
                         assert_eq!(gns.latitude, None);
                         assert_eq!(gns.longitude, None);
                         assert_eq!(gns.gps_mode, GnsModeIndicator::Invalid);
@@ -16,6 +16,7 @@ limitations under the License.
 use super::*;
 /// GSA - GNSS dilution of position (DOP) and active satellites
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GsaData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -41,6 +42,7 @@ pub struct GsaData {
 
 /// GSA position fix type
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GsaFixMode {
     /// No fix.
     NotAvailable,
@@ -113,6 +115,35 @@ pub(crate) fn handle(
     }))
 }
 
+impl GsaData {
+    /// Format the data as xxGSA sentence with the given talker ID (e.g. "GP"). At most 12 PRN
+    /// numbers are included.
+    pub fn to_sentence(&self, talker_id: &str) -> String {
+        let prn_numbers: Vec<String> = (0..12)
+            .map(|i| format_padded_field(self.prn_numbers.get(i), 2))
+            .collect();
+        append_checksum(&format!(
+            "${}GSA,{},{},{},{},{},{}",
+            talker_id,
+            match self.mode1_automatic {
+                Some(true) => "A",
+                Some(false) => "M",
+                None => "",
+            },
+            match self.mode2_3d {
+                Some(GsaFixMode::NotAvailable) => "1",
+                Some(GsaFixMode::Fix2D) => "2",
+                Some(GsaFixMode::Fix3D) => "3",
+                None => "",
+            },
+            prn_numbers.join(","),
+            format_f64_field(self.pdop, 1),
+            format_f64_field(self.hdop, 1),
+            format_f64_field(self.vdop, 1),
+        ))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -147,4 +178,21 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_gsa_to_sentence() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GPGSA,A,3,19,28,14,18,27,22,31,39,,,,,1.7,1.0,1.3*34") {
+            Ok(ParsedMessage::Gsa(gsa)) => {
+                let sentence = gsa.to_sentence("GP");
+                assert_eq!(
+                    sentence,
+                    "$GPGSA,A,3,19,28,14,18,27,22,31,39,,,,,1.7,1.0,1.3*34"
+                );
+                assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Gsa(gsa)));
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
 }
@@ -17,6 +17,7 @@ use super::*;
 
 /// GSV - satellite information
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GsvData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -90,6 +91,41 @@ pub(crate) fn handle(
     }
 }
 
+impl GsvData {
+    /// Format the given satellites as xxGSV sentences with the given talker ID (e.g. "GP").
+    /// Each sentence contains up to four satellites.
+    pub fn to_sentences(satellites: &[GsvData], talker_id: &str) -> Vec<String> {
+        let pages: Vec<&[GsvData]> = if satellites.is_empty() {
+            vec![&[]]
+        } else {
+            satellites.chunks(4).collect()
+        };
+        pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| {
+                let mut sentence = format!(
+                    "${}GSV,{},{},{:02}",
+                    talker_id,
+                    pages.len(),
+                    i + 1,
+                    satellites.len()
+                );
+                for sat in page.iter() {
+                    sentence.push_str(&format!(
+                        ",{:02},{},{},{}",
+                        sat.prn_number,
+                        format_padded_field(sat.elevation.map(|v| v.round() as i32), 2),
+                        format_padded_field(sat.azimuth.map(|v| v.round() as i32), 3),
+                        format_padded_field(sat.snr.map(|v| v.round() as i32), 2),
+                    ));
+                }
+                append_checksum(&sentence)
+            })
+            .collect()
+    }
+}
+
 /// Make key for store
 fn make_gsv_key(sentence_type: &str, msg_count: u32, msg_num: u32) -> String {
     format!("{},{},{}", sentence_type, msg_count, msg_num)
@@ -176,4 +212,31 @@ mod test {
         }
         assert_eq!(p.strings_count(), 0);
     }
+
+    #[test]
+    fn test_gsv_to_sentences() {
+        let sentences = [
+            "$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74",
+            "$GPGSV,3,2,11,14,25,170,00,16,57,208,39,18,67,296,40,19,40,246,00*74",
+            "$GPGSV,3,3,11,22,42,067,42,24,14,311,43,27,05,244,00*4D",
+        ];
+        let mut p = NmeaParser::new();
+        let mut satellites = Vec::new();
+        for s in sentences.iter() {
+            if let Ok(ParsedMessage::Gsv(v)) = p.parse_sentence(s) {
+                satellites = v;
+            }
+        }
+        assert_eq!(satellites.len(), 11);
+        assert_eq!(GsvData::to_sentences(&satellites, "GP"), sentences);
+
+        // Satellite without SNR
+        satellites.truncate(1);
+        satellites[0].snr = None;
+        assert_eq!(
+            GsvData::to_sentences(&satellites, "GL"),
+            vec!["$GLGSV,1,1,01,03,03,111,*55"]
+        );
+        assert_eq!(GsvData::to_sentences(&[], "GP"), vec!["$GPGSV,1,1,00*79"]);
+    }
 }
@@ -29,6 +29,7 @@ pub(crate) mod mss;
 pub(crate) mod stn;
 pub(crate) mod vbw;
 pub(crate) mod zda;
+pub(crate) mod fix;
 
 use super::*;
 pub use gga::{GgaData, GgaQualityIndicator};
@@ -44,11 +45,13 @@ pub use mss::MssData;
 pub use stn::StnData;
 pub use vbw::VbwData;
 pub use zda::ZdaData;
+pub use fix::{GnssFix, GnssFixState, SatelliteCounts};
 
 // -------------------------------------------------------------------------------------------------
 
 /// Navigation system, identified with NMEA GNSS sentence prefix (e.g. $BDGGA)
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NavigationSystem {
     /// Combination of several satellite systems
     Combination, // GNxxx
@@ -93,6 +96,7 @@ impl std::fmt::Display for NavigationSystem {
 // -------------------------------------------------------------------------------------------------
 /// VTG/GLL FAA mode (NMEA 2.3 standard has this information)
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FaaMode {
     /// Autonomous mode (automatic 2D/3D)
     Autonomous,
@@ -117,6 +121,7 @@ impl FaaMode {
             "D" => Ok(FaaMode::Differential),
             "E" => Ok(FaaMode::Estimated),
             "N" => Ok(FaaMode::NotValid),
+            "S" => Ok(FaaMode::Simulator),
             _ => Err(format!("Unrecognized FAA information value: {}", val)),
         }
     }
@@ -129,7 +134,7 @@ impl std::fmt::Display for FaaMode {
             FaaMode::Differential => write!(f, "D"),
             FaaMode::Estimated => write!(f, "E"),
             FaaMode::NotValid => write!(f, "N"),
-            _ => write!(f, "?"),
+            FaaMode::Simulator => write!(f, "S"),
         }
     }
 }
@@ -18,6 +18,7 @@ use super::*;
 
 /// MSS - Multiple Data ID
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MssData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -17,6 +17,7 @@ use super::*;
 
 /// RMC - position, velocity, and time (Recommended Minimum sentence C)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RmcData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -59,13 +60,18 @@ impl LatLon for RmcData {
 pub(crate) fn handle(
     sentence: &str,
     nav_system: NavigationSystem,
+    store: &NmeaParser,
 ) -> Result<ParsedMessage, ParseError> {
     let split: Vec<&str> = sentence.split(',').collect();
 
     Ok(ParsedMessage::Rmc(RmcData {
         source: nav_system,
-        timestamp: parse_yymmdd_hhmmss(split.get(9).unwrap_or(&""), split.get(1).unwrap_or(&""))
-            .ok(),
+        timestamp: parse_yymmdd_hhmmss(
+            split.get(9).unwrap_or(&""),
+            split.get(1).unwrap_or(&""),
+            store.feed_time,
+        )
+        .ok(),
         status_active: {
             let s = split.get(2).unwrap_or(&"");
             match *s {
@@ -104,6 +110,33 @@ pub(crate) fn handle(
     }))
 }
 
+impl RmcData {
+    /// Format the data as xxRMC sentence with the given talker ID (e.g. "GP").
+    pub fn to_sentence(&self, talker_id: &str) -> String {
+        append_checksum(&format!(
+            "${}RMC,{},{},{},{},{},{},{},{},{}",
+            talker_id,
+            format_hhmmss_ss(self.timestamp),
+            match self.status_active {
+                Some(true) => "A",
+                Some(false) => "V",
+                None => "",
+            },
+            format_latitude_ddmm_mmmm(self.latitude),
+            format_longitude_dddmm_mmmm(self.longitude),
+            format_f64_field(self.sog_knots, 1),
+            format_f64_field(self.bearing, 1),
+            format_ddmmyy(self.timestamp),
+            format_f64_field(self.variation.map(|v| v.abs()), 1),
+            match self.variation {
+                Some(v) if v < 0.0 => "W",
+                Some(_) => "E",
+                None => "",
+            },
+        ))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -169,4 +202,22 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_rmc_to_sentence() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191120,020.3,E*67")
+        {
+            Ok(ParsedMessage::Rmc(rmc)) => {
+                let sentence = rmc.to_sentence("GP");
+                assert_eq!(
+                    sentence,
+                    "$GPRMC,225446.00,A,4916.4500,N,12311.1200,W,0.5,54.7,191120,20.3,E*49"
+                );
+                assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Rmc(rmc)));
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
 }
@@ -18,6 +18,7 @@ use super::*;
 
 /// STN - MSK Receiver Signal
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StnData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -18,6 +18,7 @@ use super::*;
 
 /// VBW - Dual Ground/Water Speed
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VbwData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -17,6 +17,7 @@ use super::*;
 
 /// VTG - track made good and speed over ground
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VtgData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -56,6 +57,21 @@ pub(crate) fn handle(
     }))
 }
 
+impl VtgData {
+    /// Format the data as xxVTG sentence with the given talker ID (e.g. "GP").
+    pub fn to_sentence(&self, talker_id: &str) -> String {
+        append_checksum(&format!(
+            "${}VTG,{},T,{},M,{},N,{},K,{}",
+            talker_id,
+            format_f64_field(self.cog_true, 1),
+            format_f64_field(self.cog_magnetic, 1),
+            format_f64_field(self.sog_knots, 1),
+            format_f64_field(self.sog_kph, 1),
+            self.faa_mode.map(|m| m.to_string()).unwrap_or_default(),
+        ))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -87,4 +103,18 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_vtg_to_sentence() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$BDVTG,054.7,T,034.4,M,005.5,N,010.2,K,D*31") {
+            Ok(ParsedMessage::Vtg(vtg)) => {
+                let sentence = vtg.to_sentence("BD");
+                assert_eq!(sentence, "$BDVTG,54.7,T,34.4,M,5.5,N,10.2,K,D*01");
+                assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Vtg(vtg)));
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
 }
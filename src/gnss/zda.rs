@@ -18,6 +18,7 @@ use super::*;
 
 /// ZDA - Time and date
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZdaData {
     /// Navigation system
     pub source: NavigationSystem,
@@ -26,6 +27,7 @@ pub struct ZdaData {
     pub timestamp_utc: Option<DateTime<Utc>>,
 
     /// Local time zone offset
+    #[cfg_attr(feature = "serde", serde(with = "serde_fixed_offset"))]
     pub timezone_local: Option<FixedOffset>,
 }
 
@@ -49,6 +51,44 @@ pub(crate) fn handle(
     }))
 }
 
+impl ZdaData {
+    /// Format the data as xxZDA sentence with the given talker ID (e.g. "GP").
+    pub fn to_sentence(&self, talker_id: &str) -> String {
+        let (day, month, year) = match self.timestamp_utc {
+            Some(ts) => (
+                format!("{:02}", ts.day()),
+                format!("{:02}", ts.month()),
+                format!("{:04}", ts.year()),
+            ),
+            None => ("".into(), "".into(), "".into()),
+        };
+        let (tz_hours, tz_minutes) = match self.timezone_local {
+            Some(tz) => {
+                let secs = tz.local_minus_utc();
+                (
+                    format!(
+                        "{}{:02}",
+                        if secs < 0 { "-" } else { "" },
+                        secs.abs() / 3600
+                    ),
+                    format!("{:02}", secs.abs() % 3600 / 60),
+                )
+            }
+            None => ("".into(), "".into()),
+        };
+        append_checksum(&format!(
+            "${}ZDA,{},{},{},{},{},{}",
+            talker_id,
+            format_hhmmss_ss(self.timestamp_utc),
+            day,
+            month,
+            year,
+            tz_hours,
+            tz_minutes,
+        ))
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -82,4 +122,18 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_zda_to_sentence() {
+        let mut p = NmeaParser::new();
+        match p.parse_sentence("$GPZDA,072914.00,31,05,2018,-03,00") {
+            Ok(ParsedMessage::Zda(zda)) => {
+                let sentence = zda.to_sentence("GP");
+                assert_eq!(sentence, "$GPZDA,072914.00,31,05,2018,-03,00*4D");
+                assert_eq!(p.parse_sentence(&sentence), Ok(ParsedMessage::Zda(zda)));
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
 }
@@ -32,21 +32,41 @@ use bitvec::prelude::*;
 pub use chrono;
 use chrono::prelude::*;
 use chrono::{DateTime, TimeZone};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub mod ais;
+#[cfg(feature = "tokio")]
+mod codec;
+mod cpa;
 mod error;
+mod geodesy;
 pub mod gnss;
+mod reader;
+mod strict;
+mod tag_block;
+mod tracker;
 mod util;
 
+#[cfg(feature = "tokio")]
+pub use codec::{NmeaCodec, NmeaStream};
+pub use cpa::{CollisionMonitor, CpaAlarmLimits, CpaData, ShipMotion};
 pub use error::ParseError;
+pub use reader::{NmeaReader, NmeaRecord, DEFAULT_MAX_LINE_LENGTH};
+pub use tag_block::{TagBlock, TagBlockGroup};
+pub use tracker::{TrackedVessel, VesselTracker};
+use tag_block::*;
 use util::*;
 
+/// Maximum number of sentences a single VDM/VDO message can be split into.
+const MAX_FRAGMENT_COUNT: u8 = 9;
+
 // -------------------------------------------------------------------------------------------------
 
 /// Result from function `NmeaParser::parse_sentence()`. If the given sentence represents only a
 /// partial message `ParsedMessage::Incomplete` is returned.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "data"))]
 pub enum ParsedMessage {
     /// The given sentence is only part of multi-sentence message and we need more data to
     /// create the actual result. State is stored in `NmeaParser` object.
@@ -63,12 +83,12 @@ pub enum ParsedMessage {
 
     /// AIS VDM/VDO type 6
     BinaryAddressedMessage(ais::BinaryAddressedMessage),
-    //
-    //    /// AIS VDM/VDO type 7
-    //    BinaryAcknowledge(ais::BinaryAcknowledge),
-    //
-    //    /// AIS VDM/VDO type 8
-    //    BinaryBroadcastMessage(ais::BinaryBroadcastMessage),
+
+    /// AIS VDM/VDO type 7
+    BinaryAcknowledge(ais::BinaryAcknowledge),
+
+    /// AIS VDM/VDO type 8
+    BinaryBroadcastMessage(ais::BinaryBroadcastMessage),
 
     // AIS VDM/VDO type 9
     StandardSarAircraftPositionReport(ais::StandardSarAircraftPositionReport),
@@ -97,6 +117,9 @@ pub enum ParsedMessage {
     // AIS VDM/VRO type 17
     DgnssBroadcastBinaryMessage(ais::DgnssBroadcastBinaryMessage),
 
+    // AIS VDM/VDO type 19
+    ExtendedClassBPositionReport(ais::ExtendedClassBPositionReport),
+
     // AIS VDM/VRO type 20
     DataLinkManagementMessage(ais::DataLinkManagementMessage),
 
@@ -166,18 +189,175 @@ pub trait LatLon {
     /// Return the longitude of the position contained by the object. If the position is not
     /// available return `None`.
     fn longitude(&self) -> Option<f64>;
+
+    /// Return the latitude and longitude if both are available.
+    fn lat_lon(&self) -> Option<(f64, f64)> {
+        Some((self.latitude()?, self.longitude()?))
+    }
+
+    /// Return the great-circle distance to the other position in metres using a spherical
+    /// earth model. If either position is not available return `None`.
+    fn distance_m(&self, other: &dyn LatLon) -> Option<f64> {
+        let ((lat1, lon1), (lat2, lon2)) = (self.lat_lon()?, other.lat_lon()?);
+        Some(geodesy::haversine_distance(lat1, lon1, lat2, lon2))
+    }
+
+    /// Return the great-circle distance to the other position in nautical miles.
+    fn distance_nm(&self, other: &dyn LatLon) -> Option<f64> {
+        self.distance_m(other).map(|d| d / geodesy::NAUTICAL_MILE_M)
+    }
+
+    /// Return the distance to the other position in metres on the WGS84 ellipsoid using
+    /// Vincenty's formula. If either position is not available or the computation doesn't
+    /// converge (nearly antipodal points) return `None`.
+    fn ellipsoidal_distance_m(&self, other: &dyn LatLon) -> Option<f64> {
+        let ((lat1, lon1), (lat2, lon2)) = (self.lat_lon()?, other.lat_lon()?);
+        geodesy::vincenty_distance(lat1, lon1, lat2, lon2)
+    }
+
+    /// Return the distance to the other position in nautical miles on the WGS84 ellipsoid.
+    fn ellipsoidal_distance_nm(&self, other: &dyn LatLon) -> Option<f64> {
+        self.ellipsoidal_distance_m(other)
+            .map(|d| d / geodesy::NAUTICAL_MILE_M)
+    }
+
+    /// Return the initial great-circle bearing to the other position in degrees (0-360).
+    fn initial_bearing(&self, other: &dyn LatLon) -> Option<f64> {
+        let ((lat1, lon1), (lat2, lon2)) = (self.lat_lon()?, other.lat_lon()?);
+        Some(geodesy::initial_bearing(lat1, lon1, lat2, lon2))
+    }
+
+    /// Return the great-circle bearing in degrees (0-360) when arriving to the other position.
+    fn final_bearing(&self, other: &dyn LatLon) -> Option<f64> {
+        let ((lat1, lon1), (lat2, lon2)) = (self.lat_lon()?, other.lat_lon()?);
+        Some(geodesy::final_bearing(lat1, lon1, lat2, lon2))
+    }
+
+    /// Return the rhumb line (constant bearing) distance to the other position in metres.
+    fn rhumb_distance_m(&self, other: &dyn LatLon) -> Option<f64> {
+        let ((lat1, lon1), (lat2, lon2)) = (self.lat_lon()?, other.lat_lon()?);
+        Some(geodesy::rhumb_distance(lat1, lon1, lat2, lon2))
+    }
+
+    /// Return the rhumb line distance to the other position in nautical miles.
+    fn rhumb_distance_nm(&self, other: &dyn LatLon) -> Option<f64> {
+        self.rhumb_distance_m(other)
+            .map(|d| d / geodesy::NAUTICAL_MILE_M)
+    }
+
+    /// Return the constant rhumb line bearing to the other position in degrees (0-360).
+    fn rhumb_bearing(&self, other: &dyn LatLon) -> Option<f64> {
+        let ((lat1, lon1), (lat2, lon2)) = (self.lat_lon()?, other.lat_lon()?);
+        Some(geodesy::rhumb_bearing(lat1, lon1, lat2, lon2))
+    }
+
+    /// Return the latitude and longitude of the point reached when travelling the given
+    /// distance in metres along a great circle with the given initial bearing in degrees.
+    fn destination_m(&self, bearing: f64, distance_m: f64) -> Option<(f64, f64)> {
+        let (lat, lon) = self.lat_lon()?;
+        Some(geodesy::destination_point(lat, lon, bearing, distance_m))
+    }
+
+    /// Return the latitude and longitude of the point reached when travelling the given
+    /// distance in nautical miles along a great circle with the given initial bearing.
+    fn destination_nm(&self, bearing: f64, distance_nm: f64) -> Option<(f64, f64)> {
+        self.destination_m(bearing, distance_nm * geodesy::NAUTICAL_MILE_M)
+    }
+}
+
+/// Latitude and longitude pair in degrees.
+impl LatLon for (f64, f64) {
+    fn latitude(&self) -> Option<f64> {
+        Some(self.0)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        Some(self.1)
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
 
+/// Limits for the multi-sentence state stored by `NmeaParser`. Entries exceeding the limits are
+/// evicted. Both limits are unlimited by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StoreLimits {
+    /// Maximum age of a stored entry. The age is measured from the time the sentence which
+    /// created the entry was fed to the parser.
+    pub max_age: Option<chrono::Duration>,
+
+    /// Maximum number of stored entries. The oldest entries are evicted first.
+    pub max_count: Option<usize>,
+}
+
+/// Source of the current time used by `NmeaParser` when the receive time of a sentence is not
+/// given explicitly. Time dependent decoding, such as completing the year of AIS ETA, the date
+/// of GGA, GLL and GNS timestamps and the century of RMC dates, is based on the receive time.
+/// A fixed clock makes processing of recorded data reproducible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Clock {
+    /// System time
+    System,
+
+    /// Fixed time
+    Fixed(DateTime<Utc>),
+}
+
+impl Default for Clock {
+    fn default() -> Clock {
+        Clock::System
+    }
+}
+
+impl Clock {
+    /// Return the current time of the clock.
+    pub fn now(&self) -> DateTime<Utc> {
+        match self {
+            Clock::System => Utc::now(),
+            Clock::Fixed(time) => *time,
+        }
+    }
+}
+
+/// Multi-sentence state evicted from `NmeaParser` before the message was completed.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "data"))]
+pub enum EvictedEntry {
+    /// Sentence fragment identified by its internal store key
+    Fragment(String),
+
+    /// Partial AIS type 24 static data waiting for the other part
+    VesselStaticData(ais::VesselStaticData),
+}
+
+/// Key of the age indexes of stored entries: store time and sequence number.
+type AgeKey = (DateTime<Utc>, u64);
+
+// -------------------------------------------------------------------------------------------------
+
 /// NMEA sentence parser which keeps multi-sentence state between `parse_sentence` calls.
 /// The parser tries to be as permissible as possible about the field formats because some NMEA
 /// encoders don't follow the standards strictly.
 #[derive(Clone)]
 #[cfg(not(feature = "multi-thread"))]
 pub struct NmeaParser {
-    saved_fragments: HashMap<String, String>,
-    saved_vsds: HashMap<u32, ais::VesselStaticData>,
+    saved_fragments: HashMap<String, (String, AgeKey)>,
+    saved_vsds: HashMap<u32, (ais::VesselStaticData, AgeKey)>,
+    fragment_ages: BTreeMap<AgeKey, String>,
+    vsd_ages: BTreeMap<AgeKey, u32>,
+    store_sequence: u64,
+    fragment_limits: StoreLimits,
+    vsd_limits: StoreLimits,
+    report_evictions: bool,
+    evicted: Vec<EvictedEntry>,
+    feed_time: DateTime<Utc>,
+    reference_time: Option<DateTime<Utc>>,
+    clock: Clock,
+    strict_mode: bool,
+    application_decoders: HashMap<(u16, u8), ais::application::CustomDecoder>,
 }
 
 /// NMEA sentence parser which keeps multi-sentence state between `parse_sentence` calls.
@@ -186,8 +366,20 @@ pub struct NmeaParser {
 /// Replace HashMap with DashMap to provide multi-thread safety
 #[cfg(feature = "multi-thread")]
 pub struct NmeaParser {
-    saved_fragments: dashmap::DashMap<String, String>,
-    saved_vsds: dashmap::DashMap<u32, ais::VesselStaticData>,
+    saved_fragments: dashmap::DashMap<String, (String, AgeKey)>,
+    saved_vsds: dashmap::DashMap<u32, (ais::VesselStaticData, AgeKey)>,
+    fragment_ages: BTreeMap<AgeKey, String>,
+    vsd_ages: BTreeMap<AgeKey, u32>,
+    store_sequence: u64,
+    fragment_limits: StoreLimits,
+    vsd_limits: StoreLimits,
+    report_evictions: bool,
+    evicted: Vec<EvictedEntry>,
+    feed_time: DateTime<Utc>,
+    reference_time: Option<DateTime<Utc>>,
+    clock: Clock,
+    strict_mode: bool,
+    application_decoders: HashMap<(u16, u8), ais::application::CustomDecoder>,
 }
 
 impl Default for NmeaParser {
@@ -203,6 +395,18 @@ impl NmeaParser {
         NmeaParser {
             saved_fragments: HashMap::new(),
             saved_vsds: HashMap::new(),
+            fragment_ages: BTreeMap::new(),
+            vsd_ages: BTreeMap::new(),
+            store_sequence: 0,
+            fragment_limits: StoreLimits::default(),
+            vsd_limits: StoreLimits::default(),
+            report_evictions: false,
+            evicted: Vec::new(),
+            feed_time: Utc::now(),
+            reference_time: None,
+            clock: Clock::System,
+            strict_mode: false,
+            application_decoders: HashMap::new(),
         }
     }
 
@@ -213,6 +417,18 @@ impl NmeaParser {
         NmeaParser {
             saved_fragments: dashmap::DashMap::new(),
             saved_vsds: dashmap::DashMap::new(),
+            fragment_ages: BTreeMap::new(),
+            vsd_ages: BTreeMap::new(),
+            store_sequence: 0,
+            fragment_limits: StoreLimits::default(),
+            vsd_limits: StoreLimits::default(),
+            report_evictions: false,
+            evicted: Vec::new(),
+            feed_time: Utc::now(),
+            reference_time: None,
+            clock: Clock::System,
+            strict_mode: false,
+            application_decoders: HashMap::new(),
         }
     }
 
@@ -221,27 +437,154 @@ impl NmeaParser {
     pub fn reset(&mut self) {
         self.saved_fragments.clear();
         self.saved_vsds.clear();
+        self.fragment_ages.clear();
+        self.vsd_ages.clear();
+        self.evicted.clear();
+        self.reference_time = None;
+    }
+
+    /// Set limits for stored sentence fragments of multi-sentence messages (VDM/VDO and GSV).
+    pub fn set_fragment_limits(&mut self, limits: StoreLimits) {
+        self.fragment_limits = limits;
+    }
+
+    /// Set limits for stored partial AIS type 24 static data.
+    pub fn set_vsd_limits(&mut self, limits: StoreLimits) {
+        self.vsd_limits = limits;
+    }
+
+    /// Enable or disable collecting of evicted entries. When enabled the entries can be
+    /// retrieved with `take_evicted`.
+    pub fn set_eviction_reporting(&mut self, enabled: bool) {
+        self.report_evictions = enabled;
+        if !enabled {
+            self.evicted.clear();
+        }
+    }
+
+    /// Return the entries evicted since the previous call and clear the list.
+    pub fn take_evicted(&mut self) -> Vec<EvictedEntry> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// Enable or disable strict mode. In strict mode sentences without a valid checksum of two
+    /// upper case hexadecimal digits are rejected, the field count and field formats of GNSS and
+    /// VDM/VDO sentences are validated and decoded values are checked against their valid
+    /// ranges. All violations of a sentence are reported in the returned error. Strict mode is
+    /// disabled by default.
+    pub fn set_strict_mode(&mut self, enabled: bool) {
+        self.strict_mode = enabled;
+    }
+
+    /// Return true if strict mode is enabled.
+    pub fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    /// Register a decoder for the binary application data of the given DAC and FID combination.
+    /// Data of AIS type 6, 8, 25 and 26 messages with the DAC and FID is passed to the decoder and
+    /// the result is returned as `ais::BinaryApplication::Custom`. A registered decoder replaces
+    /// any earlier decoder of the combination and takes precedence over the built-in decoding.
+    pub fn register_application_decoder<D>(&mut self, dac: u16, fid: u8, decoder: D)
+    where
+        D: ais::BinaryApplicationDecoder + 'static,
+    {
+        self.application_decoders.insert(
+            (dac, fid),
+            ais::application::custom_decoder(dac, fid, decoder),
+        );
+    }
+
+    /// Remove the decoder registered for the given DAC and FID combination. Returns true if a
+    /// decoder was registered.
+    pub fn unregister_application_decoder(&mut self, dac: u16, fid: u8) -> bool {
+        self.application_decoders.remove(&(dac, fid)).is_some()
+    }
+
+    /// Set the clock used as the receive time of sentences parsed with `parse_sentence` and
+    /// `parse_tagged_sentence`. The system clock is used by default.
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// Return the clock used as the receive time of sentences.
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    /// Set the date and time used to resolve the date of sentences carrying only the time of
+    /// day (GGA, GLL and GNS). The reference is updated by each RMC and ZDA sentence and by each
+    /// resolved time. When the reference is not set, the time the sentence was received is used
+    /// instead. This is useful when processing recorded data which doesn't contain RMC or ZDA
+    /// sentences.
+    pub fn set_reference_time(&mut self, time: Option<DateTime<Utc>>) {
+        self.reference_time = time;
+    }
+
+    /// Return the date and time used to resolve the date of sentences carrying only the time of
+    /// day.
+    pub fn reference_time(&self) -> Option<DateTime<Utc>> {
+        self.reference_time
+    }
+
+    /// Parse time of format HHMMSS and resolve its date from the reference time or the receive
+    /// time. The time is assumed to be within 12 hours from the reference so that midnight
+    /// rollover is handled in both directions.
+    pub(crate) fn resolve_time_of_day(
+        &mut self,
+        hhmmss: &str,
+    ) -> Result<DateTime<Utc>, ParseError> {
+        let reference = self.reference_time.unwrap_or(self.feed_time);
+        let mut time = parse_hhmmss(hhmmss, reference)?;
+        if time < reference - chrono::Duration::hours(12) {
+            time += chrono::Duration::days(1);
+        } else if time > reference + chrono::Duration::hours(12) {
+            time -= chrono::Duration::days(1);
+        }
+        if self.reference_time.is_some() {
+            self.reference_time = Some(time);
+        }
+        Ok(time)
+    }
+
+    /// Update the reference time from the full date and time of RMC and ZDA sentences.
+    fn update_reference_time(&mut self, message: &ParsedMessage) {
+        let time = match message {
+            ParsedMessage::Rmc(rmc) => rmc.timestamp,
+            ParsedMessage::Zda(zda) => zda.timestamp_utc,
+            _ => None,
+        };
+        if time.is_some() {
+            self.reference_time = time;
+        }
     }
 
     /// Push string-to-string mapping to store.
     fn push_string(&mut self, key: String, value: String) {
-        self.saved_fragments.insert(key, value);
+        let age_key = self.next_age_key();
+        self.fragment_ages.insert(age_key, key.clone());
+        if let Some((_, old_age_key)) = self.saved_fragments.insert(key, (value, age_key)) {
+            self.fragment_ages.remove(&old_age_key);
+        }
+        if exceeds_count(self.fragment_limits, self.strings_count()) {
+            self.evict_stale();
+        }
     }
 
     /// Pull string-to-string mapping by key from store.
     #[cfg(not(feature = "multi-thread"))]
     fn pull_string(&mut self, key: String) -> Option<String> {
-        self.saved_fragments.remove(&key)
+        let (v, age_key) = self.saved_fragments.remove(&key)?;
+        self.fragment_ages.remove(&age_key);
+        Some(v)
     }
     /// Pull string-to-string mapping by key from store.
     /// dashmap's remove result is Option<K, V>
     #[cfg(feature = "multi-thread")]
     fn pull_string(&mut self, key: String) -> Option<String> {
-        if let Some((_, v)) = self.saved_fragments.remove(&key) {
-            Some(v)
-        } else {
-            None
-        }
+        let (_, (v, age_key)) = self.saved_fragments.remove(&key)?;
+        self.fragment_ages.remove(&age_key);
+        Some(v)
     }
 
     /// Tests whether the given string-to-string mapping exists in the store.
@@ -256,24 +599,31 @@ impl NmeaParser {
 
     /// Push MMSI-to-VesselStaticData mapping to store.
     fn push_vsd(&mut self, mmsi: u32, vsd: ais::VesselStaticData) {
-        self.saved_vsds.insert(mmsi, vsd);
+        let age_key = self.next_age_key();
+        self.vsd_ages.insert(age_key, mmsi);
+        if let Some((_, old_age_key)) = self.saved_vsds.insert(mmsi, (vsd, age_key)) {
+            self.vsd_ages.remove(&old_age_key);
+        }
+        if exceeds_count(self.vsd_limits, self.vsds_count()) {
+            self.evict_stale();
+        }
     }
 
     /// Pull MMSI-to-VesselStaticData mapping from store.
     #[cfg(not(feature = "multi-thread"))]
     fn pull_vsd(&mut self, mmsi: u32) -> Option<ais::VesselStaticData> {
-        self.saved_vsds.remove(&mmsi)
+        let (v, age_key) = self.saved_vsds.remove(&mmsi)?;
+        self.vsd_ages.remove(&age_key);
+        Some(v)
     }
 
     /// Pull MMSI-to-VesselStaticData mapping from store.
     /// dashmap's remove result is Option<K, V>
     #[cfg(feature = "multi-thread")]
     fn pull_vsd(&mut self, mmsi: u32) -> Option<ais::VesselStaticData> {
-        if let Some((_, v)) = self.saved_vsds.remove(&mmsi) {
-            Some(v)
-        } else {
-            None
-        }
+        let (_, (v, age_key)) = self.saved_vsds.remove(&mmsi)?;
+        self.vsd_ages.remove(&age_key);
+        Some(v)
     }
 
     /// Return number of MMSI-to-VesselStaticData mappings in store.
@@ -281,11 +631,216 @@ impl NmeaParser {
         self.saved_vsds.len()
     }
 
+    /// Return the next key of the age indexes. Entries stored at the same time are ordered by
+    /// a running sequence number.
+    fn next_age_key(&mut self) -> AgeKey {
+        self.store_sequence = self.store_sequence.wrapping_add(1);
+        (self.feed_time, self.store_sequence)
+    }
+
+    /// Evict stored entries which exceed the age or count limits.
+    fn evict_stale(&mut self) {
+        let unlimited = StoreLimits::default();
+        if self.fragment_limits == unlimited && self.vsd_limits == unlimited {
+            return;
+        }
+        for key in select_evicted(&self.fragment_ages, self.fragment_limits, self.feed_time) {
+            if self.pull_string(key.clone()).is_some() {
+                debug!("Evicted stale sentence fragment: {}", key);
+                if self.report_evictions {
+                    self.evicted.push(EvictedEntry::Fragment(key));
+                }
+            }
+        }
+        for mmsi in select_evicted(&self.vsd_ages, self.vsd_limits, self.feed_time) {
+            if let Some(vsd) = self.pull_vsd(mmsi) {
+                debug!("Evicted stale static data of MMSI {}", mmsi);
+                if self.report_evictions {
+                    self.evicted.push(EvictedEntry::VesselStaticData(vsd));
+                }
+            }
+        }
+    }
+
+    /// Store the given VDM/VDO payload fragment and return the combined payload together with
+    /// the fill bits of the final fragment when all the fragments of the message have been
+    /// received. The fragments may arrive in any order.
+    fn assemble_fragments(
+        &mut self,
+        sentence_type: &str,
+        message_id: &str,
+        fragment_count: u8,
+        fragment_number: u8,
+        radio_channel_code: &str,
+        payload: String,
+        fill_bits: u8,
+    ) -> Option<(String, u8)> {
+        // Fragments are stored as "payload,fill_bits" pairs
+        self.push_string(
+            make_fragment_key(
+                sentence_type,
+                message_id,
+                fragment_count,
+                fragment_number,
+                radio_channel_code,
+            ),
+            format!("{},{}", payload, fill_bits),
+        );
+
+        for i in 1..=fragment_count {
+            let key = make_fragment_key(
+                sentence_type,
+                message_id,
+                fragment_count,
+                i,
+                radio_channel_code,
+            );
+            if !self.contains_key(key) {
+                return None;
+            }
+        }
+
+        let mut combined = String::new();
+        let mut fill_bits = 0;
+        for i in 1..=fragment_count {
+            let key = make_fragment_key(
+                sentence_type,
+                message_id,
+                fragment_count,
+                i,
+                radio_channel_code,
+            );
+            if let Some(p) = self.pull_string(key) {
+                let (fragment_payload, fragment_fill_bits) =
+                    p.split_at(p.rfind(',').unwrap_or(p.len()));
+                combined.push_str(fragment_payload);
+
+                // Only the fill bits of the final fragment are relevant
+                if i == fragment_count {
+                    fill_bits = fragment_fill_bits
+                        .get(1..)
+                        .and_then(|s| s.parse::<u8>().ok())
+                        .unwrap_or(0);
+                }
+            }
+        }
+        Some((combined, fill_bits))
+    }
+
     /// Parse NMEA sentence into `ParsedMessage` enum. If the given sentence is part of
     /// a multipart message the related state is saved into the parser and
     /// `ParsedMessage::Incomplete` is returned. The actual result is returned when all the parts
     /// have been sent to the parser.
     pub fn parse_sentence(&mut self, sentence: &str) -> Result<ParsedMessage, ParseError> {
+        self.parse_sentence_at(sentence, self.clock.now())
+    }
+
+    /// Parse NMEA sentence like `parse_sentence` but use the given time as the time the sentence
+    /// was received. The time is used to expire stored multi-sentence state, which makes
+    /// processing of recorded data reproducible.
+    pub fn parse_sentence_at(
+        &mut self,
+        sentence: &str,
+        feed_time: DateTime<Utc>,
+    ) -> Result<ParsedMessage, ParseError> {
+        self.parse_tagged_sentence_at(sentence, feed_time)
+            .map(|(message, _)| message)
+    }
+
+    /// Parse NMEA sentence which may be preceded by an NMEA 4.x TAG block (e.g.
+    /// `\s:station,c:1609459200*hh\!AIVDM,...`). The parsed message is returned together with
+    /// the TAG block. When the TAG block defines a sentence group, the TAG blocks of the group are
+    /// combined and returned with the last sentence of the group.
+    pub fn parse_tagged_sentence(
+        &mut self,
+        sentence: &str,
+    ) -> Result<(ParsedMessage, Option<TagBlock>), ParseError> {
+        self.parse_tagged_sentence_at(sentence, self.clock.now())
+    }
+
+    /// Parse NMEA sentence like `parse_tagged_sentence` but use the given time as the time the
+    /// sentence was received.
+    pub fn parse_tagged_sentence_at(
+        &mut self,
+        sentence: &str,
+        feed_time: DateTime<Utc>,
+    ) -> Result<(ParsedMessage, Option<TagBlock>), ParseError> {
+        self.feed_time = feed_time;
+        self.evict_stale();
+
+        let (tag_content, sentence) = split_tag_block(sentence)?;
+        let tag_block = match tag_content {
+            Some(content) => Some(parse_tag_block(content)?),
+            None => None,
+        };
+        let group = tag_block.as_ref().and_then(|tb| tb.group);
+        let message = self.parse_nmea_sentence(sentence, group)?;
+        if self.strict_mode {
+            let violations = strict::check_ranges(&message);
+            if !violations.is_empty() {
+                return Err(ParseError::InvalidSentence(format!(
+                    "{}: {}",
+                    violations.join(", "),
+                    sentence
+                )));
+            }
+        }
+        self.update_reference_time(&message);
+
+        // Combine the TAG blocks of a sentence group
+        if let (Some(content), Some(group)) = (tag_content, group) {
+            if let Some(combined) = self.assemble_tag_group(content, group) {
+                return Ok((message, Some(combined)));
+            }
+        }
+        Ok((message, tag_block))
+    }
+
+    /// Store the given TAG block of a sentence group and return the combined TAG block when
+    /// the TAG blocks of all the sentences in the group have been received.
+    fn assemble_tag_group(&mut self, content: &str, group: TagBlockGroup) -> Option<TagBlock> {
+        self.push_string(
+            make_tag_group_key(group.group_id, group.sentence_count, group.sentence_number),
+            content.to_string(),
+        );
+
+        for i in 1..=group.sentence_count {
+            if !self.contains_key(make_tag_group_key(group.group_id, group.sentence_count, i)) {
+                return None;
+            }
+        }
+
+        let mut combined = TagBlock::default();
+        for i in 1..=group.sentence_count {
+            if let Some(content) =
+                self.pull_string(make_tag_group_key(group.group_id, group.sentence_count, i))
+            {
+                if let Ok(tb) = parse_tag_block(&content) {
+                    combined = tb.merge(&combined);
+                }
+            }
+        }
+        Some(combined)
+    }
+
+    /// Parse NMEA sentence without TAG block. Group information of the TAG block is used to
+    /// combine VDM/VDO fragments lacking the sequential message ID.
+    fn parse_nmea_sentence(
+        &mut self,
+        sentence: &str,
+        group: Option<TagBlockGroup>,
+    ) -> Result<ParsedMessage, ParseError> {
+        if self.strict_mode {
+            let violations = strict::check_checksum(sentence);
+            if !violations.is_empty() {
+                return Err(ParseError::CorruptedSentence(format!(
+                    "{}: {}",
+                    violations.join(", "),
+                    sentence
+                )));
+            }
+        }
+
         // Calculace NMEA checksum and compare it to the given one. Also, remove the checksum part
         // from the sentence to simplify next processing steps.
         let mut checksum = 0;
@@ -294,7 +849,7 @@ impl NmeaParser {
                 if pos + 3 <= sentence.len() {
                     (
                         sentence[0..pos].to_string(),
-                        sentence[(pos + 1)..(pos + 3)].to_string(),
+                        sentence.get((pos + 1)..(pos + 3)).unwrap_or("").to_string(),
                     )
                 } else {
                     debug!("Invalid checksum found for sentence: {}", sentence);
@@ -330,8 +885,8 @@ impl NmeaParser {
 
         // Identify GNSS system by talker ID.
         let nav_system = {
-            if &sentence_type[0..1] == "$" {
-                match &sentence_type[1..3] {
+            if sentence_type.starts_with('$') {
+                match sentence_type.get(1..3).unwrap_or("") {
                     "GN" => Some(gnss::NavigationSystem::Combination),
                     "GP" => Some(gnss::NavigationSystem::Gps),
                     "GL" => Some(gnss::NavigationSystem::Glonass),
@@ -347,15 +902,17 @@ impl NmeaParser {
         };
         if nav_system != None {
             // Shorten the GNSS setence types to three letters
-            if sentence_type.len() <= 6 {
-                sentence_type = format!("${}", &sentence_type[3..6]);
+            if let Some(s) = sentence_type.get(3..6) {
+                if sentence_type.len() <= 6 {
+                    sentence_type = format!("${}", s);
+                }
             }
         }
 
         // Identify AIS station
         let station = {
-            if &sentence_type[0..1] == "!" {
-                match &sentence_type[1..3] {
+            if sentence_type.starts_with('!') {
+                match sentence_type.get(1..3).unwrap_or("") {
                     "AB" => Some(ais::Station::BaseStation),
                     "AD" => Some(ais::Station::DependentAisBaseStation),
                     "AI" => Some(ais::Station::MobileStation),
@@ -372,8 +929,21 @@ impl NmeaParser {
         };
         if station != None {
             // Shorten the AIS setence types to three letters
-            if sentence_type.len() <= 6 {
-                sentence_type = format!("!{}", &sentence_type[3..6]);
+            if let Some(s) = sentence_type.get(3..6) {
+                if sentence_type.len() <= 6 {
+                    sentence_type = format!("!{}", s);
+                }
+            }
+        }
+
+        if self.strict_mode {
+            let violations = strict::check_fields(&sentence_type, &sentence);
+            if !violations.is_empty() {
+                return Err(ParseError::InvalidSentence(format!(
+                    "{}: {}",
+                    violations.join(", "),
+                    sentence
+                )));
             }
         }
 
@@ -383,16 +953,19 @@ impl NmeaParser {
             "$GGA" => gnss::gga::handle(
                 sentence.as_str(),
                 nav_system.unwrap_or(gnss::NavigationSystem::Other),
+                self,
             ),
             // $xxRMC - Recommended minimum specific GPS/Transit data
             "$RMC" => gnss::rmc::handle(
                 sentence.as_str(),
                 nav_system.unwrap_or(gnss::NavigationSystem::Other),
+                self,
             ),
             // $xxGNS - GNSS fix data
             "$GNS" => gnss::gns::handle(
                 sentence.as_str(),
                 nav_system.unwrap_or(gnss::NavigationSystem::Other),
+                self,
             ),
             // $xxGSA - GPS DOP and active satellites
             "$GSA" => gnss::gsa::handle(
@@ -414,6 +987,7 @@ impl NmeaParser {
             "$GLL" => gnss::gll::handle(
                 sentence.as_str(),
                 nav_system.unwrap_or(gnss::NavigationSystem::Other),
+                self,
             ),
             // $xxALM - Almanac Data
             "$ALM" => gnss::alm::handle(
@@ -454,8 +1028,14 @@ impl NmeaParser {
                 let mut message_id = None;
                 let mut radio_channel_code = None;
                 let mut payload_string: String = "".into();
+                let mut full_sentence_type = "";
+                let mut fill_bits = 0;
                 for (num, s) in sentence.split(',').enumerate() {
                     match num {
+                        0 => {
+                            // Sentence type including talker ID
+                            full_sentence_type = s;
+                        }
                         1 => {
                             match s.parse::<u8>() {
                                 Ok(i) => {
@@ -476,7 +1056,7 @@ impl NmeaParser {
                                 }
                                 Err(_) => {
                                     return Err(ParseError::InvalidSentence(format!(
-                                        "Failed to parse fragment count: {}",
+                                        "Failed to parse fragment number: {}",
                                         s
                                     )));
                                 }
@@ -493,64 +1073,61 @@ impl NmeaParser {
                             payload_string = s.to_string();
                         }
                         6 => {
-                            // fill bits
+                            fill_bits = match s {
+                                "" => 0,
+                                _ => s.parse::<u8>().map_err(|_| {
+                                    ParseError::InvalidSentence(format!(
+                                        "Failed to parse fill bits: {}",
+                                        s
+                                    ))
+                                })?,
+                            };
+                            if fill_bits > 5 {
+                                return Err(ParseError::InvalidSentence(format!(
+                                    "Fill bits out of range (0-5): {}",
+                                    fill_bits
+                                )));
+                            }
                         }
                         _ => {}
                     }
                 }
 
                 // Try parse the payload
+                if fragment_count == 0 || fragment_count > MAX_FRAGMENT_COUNT {
+                    return Err(ParseError::InvalidSentence(format!(
+                        "Unsupported fragment count: {}",
+                        fragment_count
+                    )));
+                }
+                if fragment_number == 0 || fragment_number > fragment_count {
+                    return Err(ParseError::InvalidSentence(format!(
+                        "Unexpected fragment number: {}/{}",
+                        fragment_number, fragment_count
+                    )));
+                }
                 let mut bv: Option<BitVec> = None;
                 if fragment_count == 1 {
-                    bv = parse_payload(&payload_string).ok();
-                } else if fragment_count == 2 {
-                    if let Some(msg_id) = message_id {
-                        let key1 = make_fragment_key(
-                            &sentence_type.to_string(),
-                            msg_id,
-                            fragment_count,
-                            1,
-                            radio_channel_code.unwrap_or(""),
-                        );
-                        let key2 = make_fragment_key(
-                            &sentence_type.to_string(),
-                            msg_id,
-                            fragment_count,
-                            2,
-                            radio_channel_code.unwrap_or(""),
-                        );
-                        if fragment_number == 1 {
-                            if let Some(p) = self.pull_string(key2) {
-                                let mut payload_string_combined = payload_string;
-                                payload_string_combined.push_str(p.as_str());
-                                bv = parse_payload(&payload_string_combined).ok();
-                            } else {
-                                self.push_string(key1, payload_string);
-                            }
-                        } else if fragment_number == 2 {
-                            if let Some(p) = self.pull_string(key1) {
-                                let mut payload_string_combined = p;
-                                payload_string_combined.push_str(payload_string.as_str());
-                                bv = parse_payload(&payload_string_combined).ok();
-                            } else {
-                                self.push_string(key2, payload_string);
-                            }
-                        } else {
-                            warn!(
-                                "Unexpected NMEA fragment number: {}/{}",
-                                fragment_number, fragment_count
-                            );
-                        }
-                    } else {
-                        warn!(
-                            "NMEA message_id missing from {} than supported 2",
-                            sentence_type
-                        );
+                    bv = Some(parse_payload(&payload_string, fill_bits)?);
+                } else if let Some(msg_id) = message_id
+                    .map(|id| id.to_string())
+                    .or_else(|| group.map(|g| format!("g{}", g.group_id)))
+                {
+                    if let Some((p, fb)) = self.assemble_fragments(
+                        full_sentence_type,
+                        &msg_id,
+                        fragment_count,
+                        fragment_number,
+                        radio_channel_code.unwrap_or(""),
+                        payload_string,
+                        fill_bits,
+                    ) {
+                        bv = Some(parse_payload(&p, fb)?);
                     }
                 } else {
                     warn!(
-                        "NMEA sentence fragment count greater ({}) than supported 2",
-                        fragment_count
+                        "NMEA message_id missing from {} fragment {}/{}",
+                        sentence_type, fragment_number, fragment_count
                     );
                 }
 
@@ -573,30 +1150,29 @@ impl NmeaParser {
                         5 => ais::vdm_t5::handle(
                             &bv,
                             station.unwrap_or(ais::Station::Other),
+                            self,
                             own_vessel,
                         ),
                         // Addressed binary message
                         6 => ais::vdm_t6::handle(
                             &bv,
                             station.unwrap_or(ais::Station::Other),
+                            self,
                             own_vessel,
                         ),
                         // Binary acknowledge
-                        7 => {
-                            // TODO: implementation
-                            Err(ParseError::UnsupportedSentenceType(format!(
-                                "Unsupported {} message type: {}",
-                                sentence_type, message_type
-                            )))
-                        }
+                        7 => ais::vdm_t7::handle(
+                            &bv,
+                            station.unwrap_or(ais::Station::Other),
+                            own_vessel,
+                        ),
                         // Binary broadcast message
-                        8 => {
-                            // TODO: implementation
-                            Err(ParseError::UnsupportedSentenceType(format!(
-                                "Unsupported {} message type: {}",
-                                sentence_type, message_type
-                            )))
-                        }
+                        8 => ais::vdm_t8::handle(
+                            &bv,
+                            station.unwrap_or(ais::Station::Other),
+                            self,
+                            own_vessel,
+                        ),
                         // Standard SAR aircraft position report
                         9 => ais::vdm_t9::handle(
                             &bv,
@@ -698,12 +1274,14 @@ impl NmeaParser {
                         25 => ais::vdm_t25::handle(
                             &bv,
                             station.unwrap_or(ais::Station::Other),
+                            self,
                             own_vessel,
                         ),
                         // Multiple slot binary message
                         26 => ais::vdm_t26::handle(
                             &bv,
                             station.unwrap_or(ais::Station::Other),
+                            self,
                             own_vessel,
                         ),
                         // Long range AIS broadcast message
@@ -729,6 +1307,38 @@ impl NmeaParser {
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+
+/// Test whether the given number of entries exceeds the count limit.
+fn exceeds_count(limits: StoreLimits, count: usize) -> bool {
+    limits.max_count.map(|max| count > max).unwrap_or(false)
+}
+
+/// Select the keys to be evicted from the given age index. Only the oldest entries are visited.
+fn select_evicted<K: Clone>(
+    ages: &BTreeMap<AgeKey, K>,
+    limits: StoreLimits,
+    now: DateTime<Utc>,
+) -> Vec<K> {
+    let mut excess = limits
+        .max_count
+        .map(|max| ages.len().saturating_sub(max))
+        .unwrap_or(0);
+    ages.iter()
+        .take_while(|((t, _), _)| {
+            if excess > 0 {
+                excess -= 1;
+                true
+            } else {
+                limits.max_age.map(|age| now - *t > age).unwrap_or(false)
+            }
+        })
+        .map(|(_, k)| k.clone())
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -765,6 +1375,202 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_three_fragments_out_of_order() {
+        let mut p = NmeaParser::new();
+        let s1 = "!AIVDM,3,1,7,B,55?MbV02;H;s<HtKR20EHE:0@T4@Dn,0*7C";
+        let s2 = "!AIVDM,3,2,7,B,2222222216L961O5Gf0NSQEp6ClRp8,0*77";
+        let s3 = "!AIVDM,3,3,7,B,88888888880,2*20";
+
+        assert_eq!(p.parse_sentence(s3), Ok(ParsedMessage::Incomplete));
+        assert_eq!(p.parse_sentence(s1), Ok(ParsedMessage::Incomplete));
+        assert_eq!(p.strings_count(), 2);
+        match p.parse_sentence(s2) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.mmsi, 351759000);
+                assert_eq!(vsd.name, Some("EVER DIADEM".into()));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(p.strings_count(), 0);
+    }
+
+    #[test]
+    fn test_parse_invalid_fragment_count() {
+        let mut p = NmeaParser::new();
+        assert!(p.parse_sentence("!AIVDM,10,1,7,B,88888888880,2").is_err());
+        assert!(p.parse_sentence("!AIVDM,2,3,7,B,88888888880,2").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_fill_bits() {
+        let mut p = NmeaParser::new();
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,6")
+            .is_err());
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,x")
+            .is_err());
+    }
+
+    #[test]
+    fn test_fragment_expiry() {
+        let mut p = NmeaParser::new();
+        p.set_fragment_limits(StoreLimits {
+            max_age: Some(chrono::Duration::seconds(60)),
+            max_count: None,
+        });
+        p.set_eviction_reporting(true);
+        let t0 = Utc.ymd(2021, 1, 1).and_hms(12, 0, 0);
+
+        // Lone first fragment expires
+        let s1 = "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C";
+        let s2 = "!AIVDM,2,2,1,A,88888888880,2*25";
+        assert_eq!(p.parse_sentence_at(s1, t0), Ok(ParsedMessage::Incomplete));
+        assert_eq!(p.strings_count(), 1);
+        assert_eq!(
+            p.parse_sentence_at(s2, t0 + chrono::Duration::seconds(61)),
+            Ok(ParsedMessage::Incomplete)
+        );
+        assert_eq!(p.strings_count(), 1);
+        assert_eq!(
+            p.take_evicted(),
+            vec![EvictedEntry::Fragment("!AIVDM,2,1,1,A".into())]
+        );
+        assert!(p.take_evicted().is_empty());
+
+        // Fragments within the age limit are combined
+        p.reset();
+        assert_eq!(p.parse_sentence_at(s1, t0), Ok(ParsedMessage::Incomplete));
+        match p.parse_sentence_at(s2, t0 + chrono::Duration::seconds(59)) {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.mmsi, 351759000);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vsd_count_limit() {
+        let mut p = NmeaParser::new();
+        p.set_vsd_limits(StoreLimits {
+            max_age: None,
+            max_count: Some(1),
+        });
+        p.set_eviction_reporting(true);
+        let t0 = Utc.ymd(2021, 1, 1).and_hms(12, 0, 0);
+        p.feed_time = t0;
+        p.push_vsd(1, vsd(1));
+        p.feed_time = t0 + chrono::Duration::seconds(1);
+        p.push_vsd(2, vsd(2));
+        assert_eq!(p.vsds_count(), 1);
+        assert!(p.pull_vsd(2).is_some());
+        assert_eq!(
+            p.take_evicted(),
+            vec![EvictedEntry::VesselStaticData(vsd(1))]
+        );
+    }
+
+    #[test]
+    fn test_fragment_count_limit() {
+        let mut p = NmeaParser::new();
+        p.set_fragment_limits(StoreLimits {
+            max_age: None,
+            max_count: Some(2),
+        });
+        p.set_eviction_reporting(true);
+        let t0 = Utc.ymd(2021, 1, 1).and_hms(12, 0, 0);
+        p.feed_time = t0;
+        p.push_string("a".into(), "1".into());
+        p.push_string("b".into(), "2".into());
+
+        // Replacing an entry refreshes its age and does not evict anything
+        p.feed_time = t0 + chrono::Duration::seconds(1);
+        p.push_string("a".into(), "3".into());
+        assert_eq!(p.strings_count(), 2);
+        assert!(p.take_evicted().is_empty());
+
+        // Oldest entry is evicted first
+        p.push_string("c".into(), "4".into());
+        assert_eq!(p.strings_count(), 2);
+        assert_eq!(p.take_evicted(), vec![EvictedEntry::Fragment("b".into())]);
+        assert_eq!(p.pull_string("a".into()), Some("3".into()));
+        assert_eq!(p.fragment_ages.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_tagged_sentence() {
+        let mut p = NmeaParser::new();
+
+        // Single sentence
+        match p.parse_tagged_sentence(
+            "\\s:2573535,c:1671533231*08\\!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40",
+        ) {
+            Ok((ParsedMessage::VesselDynamicData(_), Some(tb))) => {
+                assert_eq!(tb.source, Some("2573535".into()));
+                assert_eq!(tb.time, Some(Utc.ymd(2022, 12, 20).and_hms(10, 47, 11)));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+
+        // Corrupted TAG block
+        assert!(p
+            .parse_tagged_sentence(
+                "\\s:2573535,c:1671533231*09\\!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40",
+            )
+            .is_err());
+
+        // Sentence group without sequential message ID
+        let s1 =
+            "\\g:1-2-1234,s:station1,c:1609459200*05\\!AIVDM,2,1,,A,55?MbV02;H;s<HtKR20EHE:0@T4@\
+                  Dn2222222216L961O5Gf0NSQEp6ClRp8,0*2D";
+        let s2 = "\\g:2-2-1234*59\\!AIVDM,2,2,,A,88888888880,2*14";
+        match p.parse_tagged_sentence(s1) {
+            Ok((ParsedMessage::Incomplete, Some(tb))) => {
+                assert_eq!(tb.source, Some("station1".into()));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        match p.parse_tagged_sentence(s2) {
+            Ok((ParsedMessage::VesselStaticData(vsd), Some(tb))) => {
+                assert_eq!(vsd.mmsi, 351759000);
+                assert_eq!(tb.source, Some("station1".into()));
+                assert_eq!(tb.time, Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)));
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(p.strings_count(), 0);
+
+        // Group ID is not mixed with a sequential message ID of the same value
+        let s1 = "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C";
+        let s2 = "\\g:2-2-1*6C\\!AIVDM,2,2,,A,88888888880,2*14";
+        assert_eq!(p.parse_sentence(s1), Ok(ParsedMessage::Incomplete));
+        match p.parse_tagged_sentence(s2) {
+            Ok((ParsedMessage::Incomplete, _)) => {}
+            _ => {
+                assert!(false);
+            }
+        }
+        assert!(p.contains_key("!AIVDM,2,1,1,A".into()));
+        assert!(p.contains_key("!AIVDM,2,2,g1,A".into()));
+        p.reset();
+
+        // TAG block is accepted by parse_sentence too
+        assert!(p
+            .parse_sentence("\\s:2573535,c:1671533231*08\\$GPZDA,072914.00,31,05,2018,-03,00")
+            .is_ok());
+    }
+
     #[test]
     fn test_nmea_parser() {
         let mut p = NmeaParser::new();
@@ -808,6 +1614,298 @@ mod test {
         assert_eq!(vsd(0).country(), None);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut p = NmeaParser::new();
+        let sentences = [
+            "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40",
+            "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+            "!AIVDM,2,2,1,A,88888888880,2*25",
+            "!AIVDM,2,1,5,A,A02VqLPA4I6C07h5Ed1h<OrsuBTTwS?r:C?w`?la<gno1RTRwSP9:BcurA8a,0*3A",
+            "!AIVDM,2,2,5,A,:Oko02TSwu8<:Jbb,0*11",
+            "!AIVDM,1,1,,A,I6SWo?8P00a3PKpEKEVj0?vNP<65,0*73",
+            "!AIVDM,1,1,,A,JB3R0GO7p>vQL8tjw0b5hqpd0706kh9d3lR2vbl0400,2*40",
+            "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47",
+            "$GPGSV,3,1,11,03,03,111,00,04,15,270,00,06,01,010,00,13,06,292,00*74",
+            "$GPGSV,3,2,11,14,25,170,00,16,57,208,39,18,67,296,40,19,40,246,00*74",
+            "$GPGSV,3,3,11,22,42,067,42,24,14,311,43,27,05,244,00,,,,*4D",
+            "$GPZDA,072914.00,31,05,2018,-03,00",
+        ];
+        let mut count = 0;
+        for s in sentences.iter() {
+            match p.parse_sentence(s) {
+                Ok(ParsedMessage::Incomplete) => {}
+                Ok(msg) => {
+                    let json = serde_json::to_string(&msg).unwrap();
+                    let msg2: ParsedMessage = serde_json::from_str(&json).unwrap();
+                    assert_eq!(msg, msg2);
+                    count += 1;
+                }
+                Err(e) => assert_eq!(e.to_string(), "OK"),
+            }
+        }
+        assert_eq!(count, 8);
+
+        // Enum tagging
+        let json = serde_json::to_string(&p.parse_sentence(sentences[11]).unwrap()).unwrap();
+        assert!(json.starts_with("{\"type\":\"Zda\",\"data\":{\"source\":\"Gps\""));
+        assert!(json.contains("\"timezone_local\":-10800"));
+    }
+
+    #[test]
+    fn test_time_of_day_date_resolution() {
+        fn timestamp(msg: ParsedMessage) -> Option<DateTime<Utc>> {
+            match msg {
+                ParsedMessage::Gga(gga) => gga.timestamp,
+                ParsedMessage::Gll(gll) => gll.timestamp,
+                ParsedMessage::Gns(gns) => gns.timestamp,
+                ParsedMessage::Rmc(rmc) => rmc.timestamp,
+                _ => None,
+            }
+        }
+
+        // Date carried over from RMC across midnight
+        let mut p = NmeaParser::new();
+        let t0 = Utc.ymd(2021, 5, 1).and_hms(12, 0, 0);
+        let sentences = [
+            "$GPRMC,235958.00,A,6010.4856,N,02450.9910,E,5.2,123.4,311220,,,A*54",
+            "$GPGGA,235959.00,6010.4856,N,02450.9910,E,1,08,0.9,12.3,M,18.0,M,,*5F",
+            "$GPGLL,6010.4856,N,02450.9910,E,000001.00,A,A*62",
+            "$GPGNS,000002.00,6010.4856,N,02450.9910,E,AN,08,0.9,12.3,18.0,,*79",
+        ];
+        let times: Vec<Option<DateTime<Utc>>> = sentences
+            .iter()
+            .map(|s| timestamp(p.parse_sentence_at(s, t0).unwrap()))
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                Some(Utc.ymd(2020, 12, 31).and_hms(23, 59, 58)),
+                Some(Utc.ymd(2020, 12, 31).and_hms(23, 59, 59)),
+                Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 1)),
+                Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 2)),
+            ]
+        );
+        assert_eq!(
+            p.reference_time(),
+            Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 2))
+        );
+
+        // Date taken from the receive time when no reference is available
+        p.reset();
+        let t1 = Utc.ymd(2021, 5, 2).and_hms(0, 0, 30);
+        assert_eq!(
+            timestamp(p.parse_sentence_at(sentences[1], t1).unwrap()),
+            Some(Utc.ymd(2021, 5, 1).and_hms(23, 59, 59))
+        );
+        assert_eq!(
+            timestamp(p.parse_sentence_at(sentences[2], t1).unwrap()),
+            Some(Utc.ymd(2021, 5, 2).and_hms(0, 0, 1))
+        );
+        assert_eq!(p.reference_time(), None);
+
+        // Explicit reference date
+        p.set_reference_time(Some(Utc.ymd(2019, 7, 15).and_hms(0, 0, 0)));
+        assert_eq!(
+            timestamp(p.parse_sentence_at(sentences[3], t1).unwrap()),
+            Some(Utc.ymd(2019, 7, 15).and_hms(0, 0, 2))
+        );
+    }
+
+    #[test]
+    fn test_clock() {
+        let s1 = "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C";
+        let s2 = "!AIVDM,2,2,1,A,88888888880,2*25";
+        let mut p = NmeaParser::new();
+        assert_eq!(p.clock(), Clock::System);
+
+        // ETA year relative to the clock
+        for (now, eta) in [
+            (
+                Utc.ymd(2020, 6, 1).and_hms(0, 0, 0),
+                Utc.ymd(2020, 5, 15).and_hms(14, 0, 30),
+            ),
+            (
+                Utc.ymd(2020, 12, 20).and_hms(0, 0, 0),
+                Utc.ymd(2021, 5, 15).and_hms(14, 0, 30),
+            ),
+        ]
+        .iter()
+        {
+            p.set_clock(Clock::Fixed(*now));
+            assert_eq!(p.parse_sentence(s1).ok(), Some(ParsedMessage::Incomplete));
+            match p.parse_sentence(s2) {
+                Ok(ParsedMessage::VesselStaticData(vsd)) => assert_eq!(vsd.eta, Some(*eta)),
+                Ok(_) => assert!(false),
+                Err(e) => assert_eq!(e.to_string(), "OK"),
+            }
+        }
+
+        // Century of RMC date and date of GGA timestamp
+        p.set_clock(Clock::Fixed(Utc.ymd(1999, 3, 1).and_hms(12, 0, 0)));
+        match p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+        {
+            Ok(ParsedMessage::Gga(gga)) => {
+                assert_eq!(gga.timestamp, Some(Utc.ymd(1999, 3, 1).and_hms(12, 35, 19)))
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+        match p.parse_sentence("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191198,020.3,E*64")
+        {
+            Ok(ParsedMessage::Rmc(rmc)) => assert_eq!(
+                rmc.timestamp,
+                Some(Utc.ymd(1998, 11, 19).and_hms(22, 54, 46))
+            ),
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
+
+    /// Test decoder returning the first byte of the application data
+    struct FirstByteDecoder;
+
+    impl ais::BinaryApplicationDecoder for FirstByteDecoder {
+        type Output = u8;
+
+        fn decode(&self, data: &BitVec) -> Option<u8> {
+            if data.len() < 8 {
+                return None;
+            }
+            Some(data.iter().take(8).fold(0, |acc, b| (acc << 1) | *b as u8))
+        }
+    }
+
+    #[test]
+    fn test_application_decoder() {
+        let s6 = "!AIVDM,1,1,,B,6B?n;be:cbapalgc;i6?Ow4,2*4A";
+        let s8 = "!AIVDM,1,1,,A,85Mwp`1Kf3aCnsNvBWLi=wQuNhA5t43N`5nCuI=p<IBfVqnMgPGs,0*47";
+        let s25 = "!AIVDM,1,1,,A,I8IRGB40QPPa0:<HP::V=gwv0l48,0*0E";
+        let s25_addressed = "!AIVDM,1,1,,A,I1mg=5OcNJ;4rjbg=,0*5B";
+        let s26 = "!AIVDM,1,1,,A,JB3R0GO7p>vQL8tjw0b5hqpd0706kh9d3lR2vbl0400,2*40";
+        let s26_broadcast = "!AIVDM,1,1,,A,J1mg=5EKS5`t0000,4*27";
+        let mut p = NmeaParser::new();
+        p.register_application_decoder(669, 11, FirstByteDecoder);
+        p.register_application_decoder(366, 56, FirstByteDecoder);
+        p.register_application_decoder(2, 6, FirstByteDecoder);
+        p.register_application_decoder(235, 10, FirstByteDecoder);
+        p.register_application_decoder(368, 35, FirstByteDecoder);
+        p.register_application_decoder(366, 12, FirstByteDecoder);
+
+        // Type 6
+        match p.parse_sentence(s6) {
+            Ok(ParsedMessage::BinaryAddressedMessage(bam)) => match bam.application {
+                Some(ais::BinaryApplication::Custom(custom)) => {
+                    assert_eq!(custom.dac(), 669);
+                    assert_eq!(custom.fid(), 11);
+                    assert_eq!(custom.downcast_ref::<u8>(), Some(&0xeb));
+                    assert_eq!(custom.downcast_ref::<u16>(), None);
+                }
+                _ => assert!(false),
+            },
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+
+        // Type 8
+        match p.parse_sentence(s8) {
+            Ok(ParsedMessage::BinaryBroadcastMessage(bbm)) => match bbm.application {
+                Some(ais::BinaryApplication::Custom(custom)) => {
+                    assert_eq!(custom.downcast_ref::<u8>(), Some(&0x3a))
+                }
+                _ => assert!(false),
+            },
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+
+        // Type 25, broadcast
+        match p.parse_sentence(s25) {
+            Ok(ParsedMessage::SingleSlotBinaryMessage(ssbm)) => match ssbm.application {
+                Some(ais::BinaryApplication::Custom(custom)) => {
+                    assert_eq!(custom.dac(), 2);
+                    assert_eq!(custom.fid(), 6);
+                    assert_eq!(custom.downcast_ref::<u8>(), Some(&0x08))
+                }
+                _ => assert!(false),
+            },
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+
+        // Type 25, addressed
+        match p.parse_sentence(s25_addressed) {
+            Ok(ParsedMessage::SingleSlotBinaryMessage(ssbm)) => {
+                assert_eq!(ssbm.dest_mmsi, Some(987654321));
+                match ssbm.application {
+                    Some(ais::BinaryApplication::Custom(custom)) => {
+                        assert_eq!(custom.dac(), 235);
+                        assert_eq!(custom.fid(), 10);
+                        assert_eq!(custom.downcast_ref::<u8>(), Some(&0xab))
+                    }
+                    _ => assert!(false),
+                }
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+
+        // Type 26, addressed
+        match p.parse_sentence(s26) {
+            Ok(ParsedMessage::MultipleSlotBinaryMessage(msbm)) => match msbm.application {
+                Some(ais::BinaryApplication::Custom(custom)) => {
+                    assert_eq!(custom.dac(), 368);
+                    assert_eq!(custom.fid(), 35);
+                    assert_eq!(custom.downcast_ref::<u8>(), Some(&0xcc))
+                }
+                _ => assert!(false),
+            },
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+
+        // Type 26, broadcast
+        match p.parse_sentence(s26_broadcast) {
+            Ok(ParsedMessage::MultipleSlotBinaryMessage(msbm)) => {
+                assert_eq!(msbm.dest_mmsi, None);
+                assert_eq!(msbm.data.len(), 16);
+                match msbm.application {
+                    Some(ais::BinaryApplication::Custom(custom)) => {
+                        assert_eq!(custom.dac(), 366);
+                        assert_eq!(custom.fid(), 12);
+                        assert_eq!(custom.downcast_ref::<u8>(), Some(&0x5a))
+                    }
+                    _ => assert!(false),
+                }
+            }
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+
+        // Custom data is compared by value
+        assert_eq!(p.parse_sentence(s6), p.parse_sentence(s6));
+
+        // Only DAC and FID of custom data are serialized
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&p.parse_sentence(s6).unwrap()).unwrap();
+            assert_eq!(
+                json["data"]["application"],
+                serde_json::json!({"type": "Custom", "data": {"dac": 669, "fid": 11}})
+            );
+        }
+
+        // Unregistered decoder
+        assert!(p.unregister_application_decoder(366, 56));
+        assert!(!p.unregister_application_decoder(366, 56));
+        match p.parse_sentence(s8) {
+            Ok(ParsedMessage::BinaryBroadcastMessage(bbm)) => assert_eq!(bbm.application, None),
+            Ok(_) => assert!(false),
+            Err(e) => assert_eq!(e.to_string(), "OK"),
+        }
+    }
+
     /// Create a `VesselStaticData` with the given MMSI
     fn vsd(mmsi: u32) -> ais::VesselStaticData {
         let mut vsd = ais::VesselStaticData::default();
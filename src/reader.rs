@@ -0,0 +1,312 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Streaming NMEA reader
+
+use super::*;
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// Default maximum line length accepted by `NmeaReader`. NMEA 0183 sentences are at most 82
+/// characters long but TAG blocks and proprietary extensions may make the lines longer.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 1024;
+
+/// Maximum number of lines of incomplete messages kept for `NmeaRecord::lines`.
+const MAX_PENDING_LINES: usize = 256;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Result of one message read by `NmeaReader`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NmeaRecord {
+    /// The parsed message or the error which occurred.
+    pub result: Result<ParsedMessage, ParseError>,
+
+    /// Line numbers (starting from 1) and raw sentences the message was parsed from. Multi-sentence
+    /// messages include all their sentences in the order they were read.
+    pub lines: Vec<(usize, String)>,
+}
+
+/// Iterator which reads NMEA sentences line by line from the given `BufRead` and parses them
+/// with `NmeaParser`. Blank lines and incomplete multi-sentence messages are skipped. CR, LF
+/// and CR LF line endings are accepted. Over-long lines and lines containing binary data are
+/// reported as errors.
+pub struct NmeaReader<R: BufRead> {
+    reader: R,
+    parser: NmeaParser,
+    max_line_length: usize,
+    line_number: usize,
+    last_was_cr: bool,
+    pending: VecDeque<(String, usize, String)>,
+}
+
+impl<R: BufRead> NmeaReader<R> {
+    /// Construct a reader with a new `NmeaParser`.
+    pub fn new(reader: R) -> NmeaReader<R> {
+        NmeaReader::with_parser(reader, NmeaParser::new())
+    }
+
+    /// Construct a reader which uses the given parser.
+    pub fn with_parser(reader: R, parser: NmeaParser) -> NmeaReader<R> {
+        NmeaReader {
+            reader,
+            parser,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            line_number: 0,
+            last_was_cr: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Set the maximum accepted line length in bytes.
+    pub fn set_max_line_length(&mut self, max_line_length: usize) {
+        self.max_line_length = max_line_length;
+    }
+
+    /// Return a reference to the parser.
+    pub fn parser(&self) -> &NmeaParser {
+        &self.parser
+    }
+
+    /// Return a mutable reference to the parser.
+    pub fn parser_mut(&mut self) -> &mut NmeaParser {
+        &mut self.parser
+    }
+
+    /// Return the inner parser.
+    pub fn into_parser(self) -> NmeaParser {
+        self.parser
+    }
+
+    /// Read the next line without line terminator. The returned flag is true if the line was
+    /// truncated because of the length limit. `None` is returned at the end of input.
+    fn read_line(&mut self) -> std::io::Result<Option<(Vec<u8>, bool)>> {
+        let mut line = Vec::new();
+        let mut too_long = false;
+        loop {
+            let (terminated, used) = {
+                let available = match self.reader.fill_buf() {
+                    Ok(buf) => buf,
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                };
+                if available.is_empty() {
+                    // End of input
+                    if line.is_empty() && !too_long {
+                        return Ok(None);
+                    }
+                    self.line_number += 1;
+                    return Ok(Some((line, too_long)));
+                }
+
+                // Skip LF following CR
+                if self.last_was_cr && available[0] == b'\n' && line.is_empty() && !too_long {
+                    self.last_was_cr = false;
+                    (false, 1)
+                } else {
+                    let end = available
+                        .iter()
+                        .position(|b| *b == b'\n' || *b == b'\r')
+                        .unwrap_or(available.len());
+                    let room = self.max_line_length.saturating_sub(line.len());
+                    if end > room {
+                        too_long = true;
+                    }
+                    line.extend_from_slice(&available[..end.min(room)]);
+                    if end < available.len() {
+                        self.last_was_cr = available[end] == b'\r';
+                        (true, end + 1)
+                    } else {
+                        self.last_was_cr = false;
+                        (false, end)
+                    }
+                }
+            };
+            self.reader.consume(used);
+            if terminated {
+                self.line_number += 1;
+                return Ok(Some((line, too_long)));
+            }
+        }
+    }
+
+    /// Take the pending lines belonging to the message identified by the given key.
+    fn take_pending(&mut self, key: &str) -> Vec<(usize, String)> {
+        let mut lines = Vec::new();
+        let mut rest = VecDeque::new();
+        for (k, num, line) in self.pending.drain(..) {
+            if k == key {
+                lines.push((num, line));
+            } else {
+                rest.push_back((k, num, line));
+            }
+        }
+        self.pending = rest;
+        lines
+    }
+}
+
+impl<R: BufRead> Iterator for NmeaReader<R> {
+    type Item = std::io::Result<NmeaRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (raw, too_long) = match self.read_line() {
+                Ok(Some(l)) => l,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+            let line_number = self.line_number;
+
+            // Reject over-long lines and binary data
+            if too_long {
+                return Some(Ok(NmeaRecord {
+                    result: Err(ParseError::InvalidSentence(format!(
+                        "Line {} exceeds maximum length of {} bytes",
+                        line_number, self.max_line_length
+                    ))),
+                    lines: vec![(line_number, String::from_utf8_lossy(&raw).into())],
+                }));
+            }
+            if raw
+                .iter()
+                .any(|b| !(b.is_ascii_graphic() || *b == b' ' || *b == b'\t'))
+            {
+                return Some(Ok(NmeaRecord {
+                    result: Err(ParseError::InvalidSentence(format!(
+                        "Binary data on line {}",
+                        line_number
+                    ))),
+                    lines: vec![(line_number, String::from_utf8_lossy(&raw).into())],
+                }));
+            }
+
+            // Skip blank lines
+            let line = String::from_utf8_lossy(&raw).trim().to_string();
+            if line.is_empty() {
+                continue;
+            }
+
+            let key = make_pending_key(&line);
+            match self.parser.parse_sentence(&line) {
+                Ok(ParsedMessage::Incomplete) => {
+                    self.pending.push_back((key, line_number, line));
+                    if self.pending.len() > MAX_PENDING_LINES {
+                        self.pending.pop_front();
+                    }
+                }
+                result => {
+                    let mut lines = self.take_pending(&key);
+                    lines.push((line_number, line));
+                    return Some(Ok(NmeaRecord { result, lines }));
+                }
+            }
+        }
+    }
+}
+
+/// Make a key which associates the sentences of a multi-sentence message together. VDM/VDO
+/// sentences are identified by sentence type, sequential message ID and radio channel, and other
+/// sentences by the sentence type only.
+fn make_pending_key(line: &str) -> String {
+    let sentence = split_tag_block(line).map(|(_, s)| s).unwrap_or(line);
+    let split: Vec<&str> = sentence.split(',').collect();
+    let sentence_type = split.first().unwrap_or(&"");
+    if sentence_type.starts_with('!') {
+        format!(
+            "{},{},{}",
+            sentence_type,
+            split.get(3).unwrap_or(&""),
+            split.get(4).unwrap_or(&"")
+        )
+    } else {
+        sentence_type.to_string()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_nmea_reader() {
+        let input = "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40\r\n\
+                     \r\n\
+                     !AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C\r\
+                     $GPZDA,072914.00,31,05,2018,-03,00\n\
+                     !AIVDM,2,2,1,A,88888888880,2*25\n\
+                     garbage\n\
+                     $GPZDA,\x01\x02\n\
+                     $GPZDA,072914.00,31,05,2018,-03,00";
+        let records: Vec<NmeaRecord> = NmeaReader::new(Cursor::new(input))
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(records.len(), 6);
+
+        match &records[0].result {
+            Ok(ParsedMessage::VesselDynamicData(_)) => {}
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(records[0].lines[0].0, 1);
+
+        match &records[1].result {
+            Ok(ParsedMessage::Zda(_)) => {}
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(records[1].lines.len(), 1);
+        assert_eq!(records[1].lines[0].0, 4);
+
+        match &records[2].result {
+            Ok(ParsedMessage::VesselStaticData(vsd)) => {
+                assert_eq!(vsd.mmsi, 351759000);
+            }
+            _ => {
+                assert!(false);
+            }
+        }
+        assert_eq!(
+            records[2].lines.iter().map(|(n, _)| *n).collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+
+        assert!(records[3].result.is_err());
+        assert_eq!(records[3].lines, vec![(6, "garbage".to_string())]);
+        assert!(records[4].result.is_err());
+        assert_eq!(records[4].lines[0].0, 7);
+        assert!(records[5].result.is_ok());
+        assert_eq!(records[5].lines[0].0, 8);
+    }
+
+    #[test]
+    fn test_nmea_reader_long_line() {
+        let input = format!("{}\n$GPZDA,072914.00,31,05,2018,-03,00\n", "A".repeat(100));
+        let mut reader = NmeaReader::new(Cursor::new(input));
+        reader.set_max_line_length(50);
+        let records: Vec<NmeaRecord> = reader.map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].result.is_err());
+        assert_eq!(records[0].lines[0].1.len(), 50);
+        assert!(records[1].result.is_ok());
+        assert_eq!(records[1].lines[0].0, 2);
+    }
+}
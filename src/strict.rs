@@ -0,0 +1,345 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Strict mode validation of sentence fields and decoded values
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Field layouts of the supported sentence types. The tuple contains the sentence type, the
+/// minimum number of fields after the sentence type and a kind character for each known field:
+///
+/// - `t`: time of format HHMMSS or HHMMSS.SS
+/// - `d`: date of format DDMMYY
+/// - `y`: latitude of format DDMM.MMM
+/// - `x`: longitude of format DDDMM.MMM
+/// - `N`: hemisphere N or S
+/// - `E`: hemisphere E or W
+/// - `A`: status A or V
+/// - `c`: single letter
+/// - `f`: decimal number
+/// - `i`: integer
+/// - `u`: unsigned integer
+/// - `h`: hexadecimal number
+/// - `*`: any content
+///
+/// GSV sentences have a variable number of satellite blocks and are handled separately.
+const FIELD_LAYOUTS: &[(&str, usize, &str)] = &[
+    ("$ALM", 15, "uuuuhhhhhhhhhhh"),
+    ("$DTM", 8, "**fNfEf*"),
+    ("$GGA", 14, "tyNxEuuffcfcfu"),
+    ("$GLL", 6, "yNxEtAc"),
+    ("$GNS", 12, "tyNxE*uffffuc"),
+    ("$GSA", 17, "cuuuuuuuuuuuuufffh"),
+    ("$MSS", 5, "fffuu"),
+    ("$RMC", 11, "tAyNxEffdfEcc"),
+    ("$STN", 1, "u"),
+    ("$VBW", 6, "ffAffAfAfA"),
+    ("$VTG", 8, "fcfcfcfcc"),
+    ("$ZDA", 6, "tuuuiu"),
+    ("!VDM", 6, "uuu**u"),
+    ("!VDO", 6, "uuu**u"),
+];
+
+/// Return the violations of the checksum part of the given sentence. A checksum of exactly two
+/// upper case hexadecimal digits is required at the end of the sentence.
+pub(crate) fn check_checksum(sentence: &str) -> Vec<String> {
+    match sentence.rfind('*') {
+        Some(pos) => {
+            let hex = &sentence[pos + 1..];
+            if hex.len() != 2
+                || !hex
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+            {
+                vec![format!(
+                    "checksum must be two upper case hexadecimal digits: {}",
+                    hex
+                )]
+            } else {
+                Vec::new()
+            }
+        }
+        None => vec!["checksum missing".to_string()],
+    }
+}
+
+/// Return the violations of field count and field formats of the given sentence without
+/// checksum. Unknown sentence types are not checked.
+pub(crate) fn check_fields(sentence_type: &str, sentence: &str) -> Vec<String> {
+    let split: Vec<&str> = sentence.split(',').collect();
+    let field_count = split.len() - 1;
+    let mut violations = Vec::new();
+
+    let (min_count, kinds): (usize, Vec<char>) = if sentence_type == "$GSV" {
+        // Three header fields followed by blocks of PRN, elevation, azimuth and SNR, and
+        // an optional signal ID
+        if field_count < 3 || (field_count - 3) % 4 > 1 {
+            violations.push(format!("invalid number of fields: {}", field_count));
+        }
+        let kinds = "uuu"
+            .chars()
+            .chain("ufff".chars().cycle().take(field_count.saturating_sub(3)))
+            .collect();
+        (3, kinds)
+    } else {
+        match FIELD_LAYOUTS.iter().find(|(t, _, _)| *t == sentence_type) {
+            Some((_, min_count, kinds)) => (*min_count, kinds.chars().collect()),
+            None => return violations,
+        }
+    };
+
+    if field_count < min_count {
+        violations.push(format!(
+            "too few fields: {} (at least {} expected)",
+            field_count, min_count
+        ));
+    } else if field_count > kinds.len() {
+        violations.push(format!(
+            "too many fields: {} (at most {} expected)",
+            field_count,
+            kinds.len()
+        ));
+    }
+
+    for (i, (field, kind)) in split.iter().skip(1).zip(kinds.iter()).enumerate() {
+        if !field.is_empty() && !check_field(field, *kind) {
+            violations.push(format!("malformed field {}: {}", i + 1, field));
+        }
+    }
+    violations
+}
+
+/// Return true if the given non-empty field is valid for the given kind.
+fn check_field(field: &str, kind: char) -> bool {
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let fraction = |s: &str, int_len: usize| {
+        let mut parts = s.splitn(2, '.');
+        let int = parts.next().unwrap_or("");
+        int.len() == int_len && digits(int) && parts.next().map(digits).unwrap_or(true)
+    };
+    match kind {
+        't' => {
+            fraction(field, 6) && field[0..2] < *"24" && field[2..4] < *"60" && field[4..6] <= *"60"
+        }
+        'd' => {
+            field.len() == 6
+                && digits(field)
+                && ("01"..="31").contains(&&field[0..2])
+                && ("01"..="12").contains(&&field[2..4])
+        }
+        'y' => fraction(field, 4) && field[2..4] < *"60",
+        'x' => fraction(field, 5) && field[3..5] < *"60",
+        'N' => field == "N" || field == "S",
+        'E' => field == "E" || field == "W",
+        'A' => field == "A" || field == "V",
+        'c' => field.len() == 1 && field.chars().all(|c| c.is_ascii_alphabetic()),
+        'f' => field.parse::<f64>().is_ok() && field.chars().all(|c| c != 'e' && c != 'E'),
+        'i' => field.parse::<i64>().is_ok(),
+        'u' => digits(field),
+        'h' => !field.is_empty() && field.chars().all(|c| c.is_ascii_hexdigit()),
+        _ => true,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Return the violations of value ranges in the given decoded message.
+pub(crate) fn check_ranges(message: &ParsedMessage) -> Vec<String> {
+    let mut violations = Vec::new();
+    let mut check = |name: &str, value: Option<f64>, min: f64, max: f64| {
+        if let Some(v) = value {
+            if v < min || v > max {
+                violations.push(format!("{} out of range: {}", name, v));
+            }
+        }
+    };
+
+    let position: Option<&dyn LatLon> = match message {
+        ParsedMessage::VesselDynamicData(vdd) => {
+            check("speed over ground", vdd.sog_knots, 0.0, 102.2);
+            check("course over ground", vdd.cog, 0.0, 359.9);
+            check("true heading", vdd.heading_true, 0.0, 359.0);
+            Some(vdd)
+        }
+        ParsedMessage::BaseStationReport(bsr) | ParsedMessage::UtcDateResponse(bsr) => Some(bsr),
+        ParsedMessage::StandardSarAircraftPositionReport(sar) => {
+            check("course over ground", sar.cog, 0.0, 359.9);
+            Some(sar)
+        }
+        ParsedMessage::ExtendedClassBPositionReport(ecbpr) => {
+            check(
+                "speed over ground",
+                ecbpr.dynamic_data.sog_knots,
+                0.0,
+                102.2,
+            );
+            check("course over ground", ecbpr.dynamic_data.cog, 0.0, 359.9);
+            check("true heading", ecbpr.dynamic_data.heading_true, 0.0, 359.0);
+            Some(ecbpr)
+        }
+        ParsedMessage::AidToNavigationReport(aton) => Some(aton),
+        ParsedMessage::Gga(gga) => {
+            check("HDOP", gga.hdop, 0.0, f64::MAX);
+            Some(gga)
+        }
+        ParsedMessage::Rmc(rmc) => {
+            check("speed over ground", rmc.sog_knots, 0.0, f64::MAX);
+            check("course over ground", rmc.bearing, 0.0, 360.0);
+            check("magnetic variation", rmc.variation, -180.0, 180.0);
+            Some(rmc)
+        }
+        ParsedMessage::Gns(gns) => {
+            check("HDOP", gns.hdop, 0.0, f64::MAX);
+            Some(gns)
+        }
+        ParsedMessage::Gll(gll) => Some(gll),
+        ParsedMessage::Gsa(gsa) => {
+            check("PDOP", gsa.pdop, 0.0, f64::MAX);
+            check("HDOP", gsa.hdop, 0.0, f64::MAX);
+            check("VDOP", gsa.vdop, 0.0, f64::MAX);
+            None
+        }
+        ParsedMessage::Gsv(gsv) => {
+            for sat in gsv {
+                check("elevation", sat.elevation.map(f64::from), 0.0, 90.0);
+                check("azimuth", sat.azimuth.map(f64::from), 0.0, 359.0);
+                check("SNR", sat.snr.map(f64::from), 0.0, 99.0);
+            }
+            None
+        }
+        ParsedMessage::Vtg(vtg) => {
+            check("true course", vtg.cog_true, 0.0, 360.0);
+            check("magnetic course", vtg.cog_magnetic, 0.0, 360.0);
+            check("speed over ground", vtg.sog_knots, 0.0, f64::MAX);
+            check("speed over ground", vtg.sog_kph, 0.0, f64::MAX);
+            None
+        }
+        _ => None,
+    };
+    if let Some(position) = position {
+        check("latitude", position.latitude(), -90.0, 90.0);
+        check("longitude", position.longitude(), -180.0, 180.0);
+    }
+    violations
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_strict_mode() {
+        let gga = "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47";
+        let mut p = NmeaParser::new();
+        assert!(!p.strict_mode());
+
+        // Permissive mode
+        assert!(p.parse_sentence(gga).is_ok());
+        assert!(p.parse_sentence(&gga[..gga.len() - 3]).is_ok());
+        assert!(p.parse_sentence(&gga.replace("*47", "*4")).is_ok());
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4a")
+            .is_err());
+        match p.parse_sentence("$GPGSV,1,1,02,01,4x,083,46,03,95,150,44*34") {
+            Ok(ParsedMessage::Gsv(gsv)) => {
+                assert_eq!(gsv[0].elevation, None);
+                assert_eq!(gsv[1].elevation, Some(95.0));
+            }
+            _ => assert!(false),
+        }
+
+        // Strict mode
+        p.set_strict_mode(true);
+        assert!(p.strict_mode());
+        assert!(p.parse_sentence(gga).is_ok());
+        assert!(p
+            .parse_sentence("$GPGSV,1,1,02,01,40,083,46,03,60,150,44*76")
+            .is_ok());
+        assert!(p
+            .parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A")
+            .is_ok());
+        assert_eq!(
+            p.parse_sentence(&gga[..gga.len() - 3]),
+            Err(ParseError::CorruptedSentence(format!(
+                "checksum missing: {}",
+                &gga[..gga.len() - 3]
+            )))
+        );
+        assert_eq!(
+            p.parse_sentence("!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4a")
+                .map_err(|e| e.to_string()),
+            Err(
+                "Corrupted NMEA sentence: checksum must be two upper case hexadecimal digits: 4a: \
+                 !AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4a"
+                    .into()
+            )
+        );
+        assert!(p.parse_sentence(&gga.replace("*47", "*4")).is_err());
+        assert_eq!(
+            p.parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M*1F"),
+            Err(ParseError::InvalidSentence(
+                "too few fields: 10 (at least 14 expected): \
+                 $GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M"
+                    .into()
+            ))
+        );
+        assert_eq!(
+            p.parse_sentence("$GPGGA,1235,4807.038,X,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*59"),
+            Err(ParseError::InvalidSentence(
+                "malformed field 1: 1235, malformed field 3: X: \
+                 $GPGGA,1235,4807.038,X,01131.000,E,1,08,0.9,545.4,M,46.9,M,,"
+                    .into()
+            ))
+        );
+        assert_eq!(
+            p.parse_sentence("$GPGSV,1,1,02,01,4x,083,46,03,95,150,44*34"),
+            Err(ParseError::InvalidSentence(
+                "malformed field 5: 4x: $GPGSV,1,1,02,01,4x,083,46,03,95,150,44".into()
+            ))
+        );
+        assert_eq!(
+            p.parse_sentence("$GPGGA,123519,9107.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*43"),
+            Err(ParseError::InvalidSentence(
+                "latitude out of range: 91.1173: \
+                 $GPGGA,123519,9107.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*43"
+                    .into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_check_ranges() {
+        let vdd = ais::VesselDynamicData {
+            sog_knots: Some(102.3),
+            cog: Some(360.0),
+            heading_true: Some(359.0),
+            longitude: Some(-181.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            check_ranges(&ParsedMessage::VesselDynamicData(vdd)),
+            vec![
+                "speed over ground out of range: 102.3",
+                "course over ground out of range: 360",
+                "longitude out of range: -181",
+            ]
+        );
+    }
+}
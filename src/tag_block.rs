@@ -0,0 +1,253 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! NMEA 4.x TAG block data structures
+
+use super::*;
+
+// -------------------------------------------------------------------------------------------------
+
+/// TAG block preceding an NMEA sentence, e.g. `\s:station,c:1609459200*hh\`.
+#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TagBlock {
+    /// Source station identifier (s)
+    pub source: Option<String>,
+
+    /// UNIX time (c) converted to UTC
+    pub time: Option<DateTime<Utc>>,
+
+    /// Destination identifier (d)
+    pub destination: Option<String>,
+
+    /// Sentence grouping (g)
+    pub group: Option<TagBlockGroup>,
+
+    /// Line count (n)
+    pub line_count: Option<u32>,
+
+    /// Relative time (r)
+    pub relative_time: Option<i64>,
+
+    /// Free text (t)
+    pub text: Option<String>,
+}
+
+/// Sentence grouping parameter of a TAG block (`g:1-2-123`)
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TagBlockGroup {
+    /// Number of the sentence within the group (1-based)
+    pub sentence_number: u8,
+
+    /// Total number of sentences in the group
+    pub sentence_count: u8,
+
+    /// Group identifier
+    pub group_id: u32,
+}
+
+impl TagBlock {
+    /// Combine two TAG blocks of the same sentence group. Fields present in `self` take
+    /// precedence over the ones in `other`.
+    pub(crate) fn merge(&self, other: &TagBlock) -> TagBlock {
+        TagBlock {
+            source: self.source.clone().or_else(|| other.source.clone()),
+            time: self.time.or(other.time),
+            destination: self
+                .destination
+                .clone()
+                .or_else(|| other.destination.clone()),
+            group: self.group.or(other.group),
+            line_count: self.line_count.or(other.line_count),
+            relative_time: self.relative_time.or(other.relative_time),
+            text: self.text.clone().or_else(|| other.text.clone()),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Split the given line into TAG block content and the actual sentence. The content is returned
+/// without the enclosing backslashes.
+pub(crate) fn split_tag_block(line: &str) -> Result<(Option<&str>, &str), ParseError> {
+    if let Some(rest) = line.strip_prefix('\\') {
+        if let Some(end) = rest.find('\\') {
+            Ok((Some(&rest[..end]), &rest[(end + 1)..]))
+        } else {
+            Err(ParseError::InvalidSentence(format!(
+                "Unterminated TAG block: {}",
+                line
+            )))
+        }
+    } else {
+        Ok((None, line))
+    }
+}
+
+/// Parse TAG block content (without the enclosing backslashes) and validate its checksum.
+pub(crate) fn parse_tag_block(content: &str) -> Result<TagBlock, ParseError> {
+    // Validate checksum
+    let fields = {
+        if let Some(pos) = content.rfind('*') {
+            let given = &content[(pos + 1)..];
+            let mut checksum = 0;
+            for c in content[0..pos].chars() {
+                checksum ^= c as u8;
+            }
+            let calculated = format!("{:02X}", checksum);
+            if !calculated.eq_ignore_ascii_case(given) {
+                return Err(ParseError::CorruptedSentence(format!(
+                    "Corrupted TAG block: {} != {}",
+                    calculated, given
+                )));
+            }
+            &content[0..pos]
+        } else {
+            debug!("No checksum found for TAG block: {}", content);
+            content
+        }
+    };
+
+    // Parse parameters
+    let mut tb = TagBlock::default();
+    for param in fields.split(',').filter(|s| !s.is_empty()) {
+        let (code, value) = {
+            if let Some(i) = param.find(':') {
+                (&param[0..i], &param[(i + 1)..])
+            } else {
+                return Err(ParseError::InvalidSentence(format!(
+                    "Invalid TAG block parameter: {}",
+                    param
+                )));
+            }
+        };
+        match code {
+            "s" => tb.source = Some(value.to_string()),
+            "c" => tb.time = Some(parse_unix_time(value)?),
+            "d" => tb.destination = Some(value.to_string()),
+            "g" => tb.group = Some(parse_group(value)?),
+            "n" => tb.line_count = Some(value.parse()?),
+            "r" => tb.relative_time = Some(value.parse()?),
+            "t" => tb.text = Some(value.to_string()),
+            _ => {
+                debug!("Unsupported TAG block parameter: {}", param);
+            }
+        }
+    }
+    Ok(tb)
+}
+
+/// Parse UNIX time given either in seconds or in milliseconds.
+fn parse_unix_time(value: &str) -> Result<DateTime<Utc>, ParseError> {
+    let raw = value.parse::<i64>()?;
+    let (secs, nanos) = {
+        if raw.abs() >= 100_000_000_000 {
+            (
+                raw.div_euclid(1000),
+                (raw.rem_euclid(1000) * 1_000_000) as u32,
+            )
+        } else {
+            (raw, 0)
+        }
+    };
+    match Utc.timestamp_opt(secs, nanos) {
+        chrono::LocalResult::Single(t) => Ok(t),
+        _ => Err(format!("Invalid TAG block UNIX time: {}", value).into()),
+    }
+}
+
+/// Parse grouping parameter of format `number-count-id`.
+fn parse_group(value: &str) -> Result<TagBlockGroup, ParseError> {
+    let split: Vec<&str> = value.split('-').collect();
+    if split.len() != 3 {
+        return Err(format!("Invalid TAG block group: {}", value).into());
+    }
+    let group = TagBlockGroup {
+        sentence_number: split[0].parse()?,
+        sentence_count: split[1].parse()?,
+        group_id: split[2].parse()?,
+    };
+    if group.sentence_number == 0 || group.sentence_number > group.sentence_count {
+        return Err(format!("Invalid TAG block group: {}", value).into());
+    }
+    Ok(group)
+}
+
+/// Make key for storing TAG blocks of sentence groups
+pub(crate) fn make_tag_group_key(group_id: u32, sentence_count: u8, sentence_number: u8) -> String {
+    format!("\\g,{},{},{}", sentence_count, sentence_number, group_id)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_tag_block() {
+        assert_eq!(
+            split_tag_block("\\s:r003669945*3A\\!AIVDM,1,1,,A,1,0*00"),
+            Ok((Some("s:r003669945*3A"), "!AIVDM,1,1,,A,1,0*00"))
+        );
+        assert_eq!(split_tag_block("$GPZDA,1"), Ok((None, "$GPZDA,1")));
+        assert!(split_tag_block("\\s:r003669945*3A").is_err());
+    }
+
+    #[test]
+    fn test_parse_tag_block() {
+        let tb = parse_tag_block("s:2573535,c:1671533231*08").unwrap();
+        assert_eq!(tb.source, Some("2573535".into()));
+        assert_eq!(tb.time, Some(Utc.ymd(2022, 12, 20).and_hms(10, 47, 11)));
+        assert_eq!(tb.group, None);
+
+        let tb = parse_tag_block("g:1-2-73874,n:157036,s:r003669945,c:1241544035*4A").unwrap();
+        assert_eq!(
+            tb.group,
+            Some(TagBlockGroup {
+                sentence_number: 1,
+                sentence_count: 2,
+                group_id: 73874,
+            })
+        );
+        assert_eq!(tb.line_count, Some(157036));
+        assert_eq!(tb.time, Some(Utc.ymd(2009, 5, 5).and_hms(17, 20, 35)));
+
+        // Milliseconds and free text
+        let tb = parse_tag_block("c:1609459200500,t:hello").unwrap();
+        assert_eq!(
+            tb.time,
+            Some(Utc.ymd(2021, 1, 1).and_hms_milli(0, 0, 0, 500))
+        );
+        assert_eq!(tb.text, Some("hello".into()));
+
+        // Corrupted and invalid
+        assert!(parse_tag_block("s:2573535,c:1671533231*09").is_err());
+        assert!(parse_tag_block("g:3-2-1").is_err());
+        assert!(parse_tag_block("foo").is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = parse_tag_block("g:1-2-1,s:abc,c:1241544035").unwrap();
+        let b = parse_tag_block("g:2-2-1,t:text").unwrap();
+        let m = b.merge(&a);
+        assert_eq!(m.source, Some("abc".into()));
+        assert_eq!(m.text, Some("text".into()));
+        assert_eq!(m.group.map(|g| g.sentence_number), Some(2));
+    }
+}
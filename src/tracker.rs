@@ -0,0 +1,394 @@
+/*
+Copyright 2020 Timo Saarinen
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! AIS vessel tracking
+
+use super::*;
+use ais::{VesselDynamicData, VesselStaticData};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Latest known state of a single vessel maintained by `VesselTracker`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackedVessel {
+    /// User ID (30 bits)
+    pub mmsi: u32,
+
+    /// Latest dynamic data (types 1, 2, 3, 18, 19 and 27)
+    pub dynamic_data: Option<VesselDynamicData>,
+
+    /// Static and voyage related data merged from all received reports (types 5, 19 and 24)
+    pub static_data: Option<VesselStaticData>,
+
+    /// Time when dynamic data was last updated
+    pub dynamic_updated: Option<DateTime<Utc>>,
+
+    /// Time when static data was last updated
+    pub static_updated: Option<DateTime<Utc>>,
+
+    /// Time when any data of the vessel was last received
+    pub last_seen: DateTime<Utc>,
+}
+
+impl TrackedVessel {
+    /// Construct a new entry without data.
+    fn new(mmsi: u32, time: DateTime<Utc>) -> TrackedVessel {
+        TrackedVessel {
+            mmsi,
+            dynamic_data: None,
+            static_data: None,
+            dynamic_updated: None,
+            static_updated: None,
+            last_seen: time,
+        }
+    }
+
+    /// Decode ISO 3166 country code from MID part of MMSI.
+    pub fn country(&self) -> Option<&'static str> {
+        match &self.static_data {
+            Some(vsd) => vsd.country(),
+            None => VesselStaticData {
+                mmsi: self.mmsi,
+                ..Default::default()
+            }
+            .country(),
+        }
+    }
+
+    /// Return the name of the vessel if known.
+    pub fn name(&self) -> Option<&str> {
+        self.static_data
+            .as_ref()
+            .and_then(|vsd| vsd.name.as_deref())
+    }
+
+    /// Update dynamic data.
+    fn update_dynamic(&mut self, vdd: &VesselDynamicData, time: DateTime<Utc>) {
+        self.dynamic_data = Some(vdd.clone());
+        self.dynamic_updated = Some(time);
+        self.last_seen = time;
+    }
+
+    /// Merge static data with the previously received one. Fields available in the new data
+    /// replace the old values.
+    fn update_static(&mut self, vsd: &VesselStaticData, time: DateTime<Utc>) {
+        self.static_data = Some(match self.static_data.take() {
+            Some(old) => merge_static_data(vsd, old),
+            None => vsd.clone(),
+        });
+        self.static_updated = Some(time);
+        self.last_seen = time;
+    }
+}
+
+impl LatLon for TrackedVessel {
+    fn latitude(&self) -> Option<f64> {
+        self.dynamic_data.as_ref().and_then(|vdd| vdd.latitude)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        self.dynamic_data.as_ref().and_then(|vdd| vdd.longitude)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Table of vessels keyed by MMSI which combines the dynamic and static data received in
+/// `ParsedMessage`s. Vessels which haven't been heard of within the limits set with
+/// `set_limits` are removed by `expire`.
+#[derive(Clone, Debug, Default)]
+pub struct VesselTracker {
+    vessels: HashMap<u32, TrackedVessel>,
+    limits: StoreLimits,
+}
+
+impl VesselTracker {
+    /// Construct an empty tracker without limits.
+    pub fn new() -> VesselTracker {
+        VesselTracker::default()
+    }
+
+    /// Set the maximum age and count of tracked vessels.
+    pub fn set_limits(&mut self, limits: StoreLimits) {
+        self.limits = limits;
+    }
+
+    /// Update the tracker with the given message using the current time as the receive time.
+    /// Returns the MMSI of the updated vessel or `None` if the message doesn't contain vessel
+    /// data.
+    pub fn update(&mut self, message: &ParsedMessage) -> Option<u32> {
+        self.update_at(message, Utc::now())
+    }
+
+    /// Update the tracker with the given message received at the given time. Returns the MMSI
+    /// of the updated vessel or `None` if the message doesn't contain vessel data.
+    pub fn update_at(&mut self, message: &ParsedMessage, time: DateTime<Utc>) -> Option<u32> {
+        match message {
+            ParsedMessage::VesselDynamicData(vdd) => {
+                self.entry(vdd.mmsi, time).update_dynamic(vdd, time);
+                Some(vdd.mmsi)
+            }
+            ParsedMessage::VesselStaticData(vsd) => {
+                self.entry(vsd.mmsi, time).update_static(vsd, time);
+                Some(vsd.mmsi)
+            }
+            ParsedMessage::ExtendedClassBPositionReport(ecbpr) => {
+                let mmsi = ecbpr.dynamic_data.mmsi;
+                let vessel = self.entry(mmsi, time);
+                vessel.update_dynamic(&ecbpr.dynamic_data, time);
+                vessel.update_static(&ecbpr.static_data, time);
+                Some(mmsi)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the vessel with the given MMSI.
+    pub fn get(&self, mmsi: u32) -> Option<&TrackedVessel> {
+        self.vessels.get(&mmsi)
+    }
+
+    /// Remove the vessel with the given MMSI.
+    pub fn remove(&mut self, mmsi: u32) -> Option<TrackedVessel> {
+        self.vessels.remove(&mmsi)
+    }
+
+    /// Return an iterator over the tracked vessels in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &TrackedVessel> {
+        self.vessels.values()
+    }
+
+    /// Return the vessels whose name contains the given string, ignoring case.
+    pub fn find_by_name(&self, name: &str) -> Vec<&TrackedVessel> {
+        let name = name.to_uppercase();
+        self.vessels
+            .values()
+            .filter(|v| v.name().map(|n| n.to_uppercase().contains(&name)) == Some(true))
+            .collect()
+    }
+
+    /// Return the number of tracked vessels.
+    pub fn len(&self) -> usize {
+        self.vessels.len()
+    }
+
+    /// Return true if no vessels are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.vessels.is_empty()
+    }
+
+    /// Remove vessels exceeding the limits using the current time. The removed vessels are
+    /// returned.
+    pub fn expire(&mut self) -> Vec<TrackedVessel> {
+        self.expire_at(Utc::now())
+    }
+
+    /// Remove vessels exceeding the limits using the given time as the current time. Vessels
+    /// not seen within the maximum age are removed first and then the least recently seen ones
+    /// until the maximum count is satisfied. The removed vessels are returned.
+    pub fn expire_at(&mut self, now: DateTime<Utc>) -> Vec<TrackedVessel> {
+        let mut by_age: Vec<(u32, DateTime<Utc>)> = self
+            .vessels
+            .values()
+            .map(|v| (v.mmsi, v.last_seen))
+            .collect();
+        by_age.sort_by_key(|(_, t)| *t);
+
+        let mut expired = Vec::new();
+        let mut remaining = by_age.len();
+        for (mmsi, last_seen) in by_age {
+            let too_old = self
+                .limits
+                .max_age
+                .map(|max_age| now - last_seen > max_age)
+                .unwrap_or(false);
+            let too_many = self
+                .limits
+                .max_count
+                .map(|max_count| remaining > max_count)
+                .unwrap_or(false);
+            if !too_old && !too_many {
+                break;
+            }
+            if let Some(vessel) = self.vessels.remove(&mmsi) {
+                expired.push(vessel);
+            }
+            remaining -= 1;
+        }
+        expired
+    }
+
+    /// Return the entry of the given vessel, creating it if needed.
+    fn entry(&mut self, mmsi: u32, time: DateTime<Utc>) -> &mut TrackedVessel {
+        self.vessels
+            .entry(mmsi)
+            .or_insert_with(|| TrackedVessel::new(mmsi, time))
+    }
+}
+
+/// Merge static data so that the fields available in `new` take precedence over `old`.
+fn merge_static_data(new: &VesselStaticData, old: VesselStaticData) -> VesselStaticData {
+    VesselStaticData {
+        own_vessel: new.own_vessel,
+        ais_type: new.ais_type,
+        mmsi: new.mmsi,
+        ais_version_indicator: new.ais_version_indicator,
+        imo_number: new.imo_number.or(old.imo_number),
+        call_sign: new.call_sign.clone().or(old.call_sign),
+        name: new.name.clone().or(old.name),
+        ship_type: if new.ship_type != ais::ShipType::NotAvailable {
+            new.ship_type
+        } else {
+            old.ship_type
+        },
+        cargo_type: if new.ship_type != ais::ShipType::NotAvailable {
+            new.cargo_type
+        } else {
+            old.cargo_type
+        },
+        equipment_vendor_id: new.equipment_vendor_id.clone().or(old.equipment_vendor_id),
+        equipment_model: new.equipment_model.or(old.equipment_model),
+        equipment_serial_number: new.equipment_serial_number.or(old.equipment_serial_number),
+        dimension_to_bow: new.dimension_to_bow.or(old.dimension_to_bow),
+        dimension_to_stern: new.dimension_to_stern.or(old.dimension_to_stern),
+        dimension_to_port: new.dimension_to_port.or(old.dimension_to_port),
+        dimension_to_starboard: new.dimension_to_starboard.or(old.dimension_to_starboard),
+        position_fix_type: new.position_fix_type.or(old.position_fix_type),
+        eta: new.eta.or(old.eta),
+        draught10: new.draught10.or(old.draught10),
+        destination: new.destination.clone().or(old.destination),
+        mothership_mmsi: new.mothership_mmsi.or(old.mothership_mmsi),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vessel_tracker() {
+        let mut p = NmeaParser::new();
+        let mut tracker = VesselTracker::new();
+        let t0 = Utc.ymd(2021, 5, 1).and_hms(12, 0, 0);
+        let sentences = [
+            "!AIVDM,1,1,,A,15RTgt0PAso;90TKcjM8h6g208CQ,0*4A",
+            "!AIVDM,2,1,1,A,55?MbV02;H;s<HtKR20EHE:0@T4@Dn2222222216L961O5Gf0NSQEp6ClRp8,0*1C",
+            "!AIVDM,2,2,1,A,88888888880,2*25",
+            "!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D",
+            "!AIVDM,1,1,,A,H42O55lti4hhhilD3nink000?050,0*40",
+            "$GPZDA,072914.00,31,05,2018,-03,00",
+        ];
+        let mut updated = Vec::new();
+        for (i, s) in sentences.iter().enumerate() {
+            let msg = p.parse_sentence(s).unwrap();
+            let time = t0 + chrono::Duration::minutes(i as i64);
+            if let Some(mmsi) = tracker.update_at(&msg, time) {
+                updated.push(mmsi);
+            }
+        }
+        assert_eq!(updated, vec![371798000, 351759000, 271041815]);
+        assert_eq!(tracker.len(), 3);
+
+        let vessel = tracker.get(371798000).unwrap();
+        assert!(vessel.dynamic_data.is_some());
+        assert_eq!(vessel.static_data, None);
+        assert_eq!(vessel.country(), Some("PA"));
+        assert_eq!(vessel.last_seen, t0);
+        assert!(vessel.latitude().is_some());
+
+        let vessel = tracker.get(271041815).unwrap();
+        assert_eq!(vessel.name(), Some("PROGUY"));
+        assert_eq!(vessel.country(), Some("TR"));
+        assert_eq!(vessel.dynamic_updated, None);
+        assert_eq!(
+            vessel.static_updated,
+            Some(t0 + chrono::Duration::minutes(4))
+        );
+
+        // Position report after static data
+        let mut vdd = tracker
+            .get(371798000)
+            .unwrap()
+            .dynamic_data
+            .clone()
+            .unwrap();
+        vdd.mmsi = 351759000;
+        let t1 = t0 + chrono::Duration::minutes(10);
+        tracker.update_at(&ParsedMessage::VesselDynamicData(vdd), t1);
+        let vessel = tracker.get(351759000).unwrap();
+        assert_eq!(vessel.name(), Some("EVER DIADEM"));
+        assert_eq!(vessel.dynamic_updated, Some(t1));
+        assert_eq!(vessel.last_seen, t1);
+
+        // Static data update keeps the fields missing from the new data
+        let vsd = VesselStaticData {
+            mmsi: 351759000,
+            destination: Some("ROTTERDAM".into()),
+            ..Default::default()
+        };
+        tracker.update_at(&ParsedMessage::VesselStaticData(vsd), t1);
+        let vsd = tracker.get(351759000).unwrap().static_data.clone().unwrap();
+        assert_eq!(vsd.name, Some("EVER DIADEM".into()));
+        assert_eq!(vsd.call_sign, Some("3FOF8".into()));
+        assert_eq!(vsd.destination, Some("ROTTERDAM".into()));
+
+        assert_eq!(tracker.find_by_name("diadem").len(), 1);
+        assert_eq!(tracker.find_by_name("titanic").len(), 0);
+        assert_eq!(tracker.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_vessel_tracker_expiry() {
+        let mut tracker = VesselTracker::new();
+        let t0 = Utc.ymd(2021, 5, 1).and_hms(12, 0, 0);
+        for i in 1..=5 {
+            let vdd = VesselDynamicData {
+                mmsi: i,
+                ..Default::default()
+            };
+            tracker.update_at(
+                &ParsedMessage::VesselDynamicData(vdd),
+                t0 + chrono::Duration::minutes(i as i64),
+            );
+        }
+
+        // No limits
+        assert!(tracker
+            .expire_at(t0 + chrono::Duration::hours(1))
+            .is_empty());
+        assert_eq!(tracker.len(), 5);
+
+        // Age limit
+        tracker.set_limits(StoreLimits {
+            max_age: Some(chrono::Duration::minutes(10)),
+            max_count: Some(2),
+        });
+        let expired = tracker.expire_at(t0 + chrono::Duration::minutes(12));
+        let mut mmsis: Vec<u32> = expired.iter().map(|v| v.mmsi).collect();
+        mmsis.sort_unstable();
+        assert_eq!(mmsis, vec![1, 2, 3]);
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.get(4).is_some());
+        assert!(tracker.get(5).is_some());
+
+        assert!(tracker.remove(4).is_some());
+        assert!(tracker.remove(4).is_none());
+        assert_eq!(tracker.len(), 1);
+    }
+}
@@ -19,10 +19,11 @@ use chrono::Duration;
 
 const AIS_CHAR_BITS: usize = 6;
 
-/// Make a key for storing NMEA sentence fragments
+/// Make a key for storing NMEA sentence fragments. The message ID is either the sequential
+/// message ID of the sentence or the group ID of the TAG block prefixed with `g`.
 pub(crate) fn make_fragment_key(
     sentence_type: &str,
-    message_id: u64,
+    message_id: &str,
     fragment_count: u8,
     fragment_number: u8,
     radio_channel_code: &str,
@@ -33,11 +34,21 @@ pub(crate) fn make_fragment_key(
     )
 }
 
-/// Convert AIS VDM/VDO payload armored string into a `BitVec`.
-pub(crate) fn parse_payload(payload: &str) -> Result<BitVec, String> {
+/// Convert AIS VDM/VDO payload armored string into a `BitVec`. The given number of fill bits
+/// is removed from the end of the result.
+pub(crate) fn parse_payload(payload: &str, fill_bits: u8) -> Result<BitVec, String> {
+    if fill_bits > 5 {
+        return Err(format!("Fill bits out of range (0-5): {}", fill_bits));
+    }
+
     let mut bv = BitVec::<LocalBits, usize>::with_capacity(payload.len() * 6);
     for c in payload.chars() {
-        let mut ci = (c as u8) - 48;
+        let mut ci = match c {
+            '0'..='W' | '`'..='w' => (c as u8) - 48,
+            _ => {
+                return Err(format!("Invalid payload character: {}", c));
+            }
+        };
         if ci > 40 {
             ci -= 8;
         }
@@ -48,9 +59,43 @@ pub(crate) fn parse_payload(payload: &str) -> Result<BitVec, String> {
         }
     }
 
+    // Remove fill bits
+    if (fill_bits as usize) > bv.len() {
+        return Err(format!(
+            "More fill bits ({}) than payload bits ({})",
+            fill_bits,
+            bv.len()
+        ));
+    }
+    bv.truncate(bv.len() - fill_bits as usize);
+
     Ok(bv)
 }
 
+/// Convert `BitVec` into AIS VDM/VDO payload armored string. The payload is padded to a
+/// multiple of six bits and the number of fill bits added is returned with the string.
+pub(crate) fn armor_payload(bv: &BitVec) -> (String, u8) {
+    let fill_bits = (AIS_CHAR_BITS - bv.len() % AIS_CHAR_BITS) % AIS_CHAR_BITS;
+    let mut res = String::with_capacity((bv.len() + fill_bits) / AIS_CHAR_BITS);
+    for i in (0..(bv.len() + fill_bits)).step_by(AIS_CHAR_BITS) {
+        let mut ci = pick_u64(bv, i, AIS_CHAR_BITS) as u8;
+        if ci > 39 {
+            ci += 8;
+        }
+        res.push((ci + 48) as char);
+    }
+    (res, fill_bits as u8)
+}
+
+/// Append NMEA checksum to the given sentence.
+pub(crate) fn append_checksum(sentence: &str) -> String {
+    let mut checksum = 0;
+    for c in sentence.chars().skip(1) {
+        checksum ^= c as u8;
+    }
+    format!("{}*{:02X}", sentence, checksum)
+}
+
 /// Pick a numberic field from `BitVec`.
 pub(crate) fn pick_u64(bv: &BitVec, index: usize, len: usize) -> u64 {
     let mut res = 0;
@@ -104,14 +149,71 @@ pub(crate) fn pick_string(bv: &BitVec, index: usize, char_count: usize) -> Strin
     res
 }
 
-/// Pick ETA based on UTC month, day, hour and minute.
-pub(crate) fn pick_eta(bv: &BitVec, index: usize) -> Result<Option<DateTime<Utc>>, ParseError> {
-    pick_eta_with_now(bv, index, Utc::now())
+/// Push a numeric field to the end of `BitVec`. Only the lowest `len` bits of the value are used.
+pub(crate) fn push_u64(bv: &mut BitVec, value: u64, len: usize) {
+    for i in (0..len).rev() {
+        bv.push((value >> i) & 0x01 != 0);
+    }
+}
+
+/// Push a signed numeric field to the end of `BitVec` in two's complement form.
+pub(crate) fn push_i64(bv: &mut BitVec, value: i64, len: usize) {
+    push_u64(bv, value as u64, len);
+}
+
+/// Push a string to the end of `BitVec`. Field `char_count` defines string length in characters.
+/// Shorter strings are padded with '@' characters and longer ones are truncated. Characters
+/// without 6-bit AIS representation are replaced with '?'.
+pub(crate) fn push_string(bv: &mut BitVec, s: &str, char_count: usize) {
+    let mut chars = s.chars();
+    for _ in 0..char_count {
+        let ch = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some(c @ ' '..='?') => c as u64,
+            Some(c @ '@'..='_') => c as u64 - 64,
+            Some(_) => '?' as u64,
+            None => 0,
+        };
+        push_u64(bv, ch, AIS_CHAR_BITS);
+    }
+}
+
+/// Push ETA to the end of `BitVec` as UTC month, day, hour and minute.
+pub(crate) fn push_eta(bv: &mut BitVec, eta: Option<DateTime<Utc>>) {
+    match eta {
+        Some(eta) => {
+            push_u64(bv, eta.month() as u64, 4);
+            push_u64(bv, eta.day() as u64, 5);
+            push_u64(bv, eta.hour() as u64, 5);
+            push_u64(bv, eta.minute() as u64, 6);
+        }
+        None => {
+            push_u64(bv, 0, 4);
+            push_u64(bv, 0, 5);
+            push_u64(bv, 24, 5);
+            push_u64(bv, 60, 6);
+        }
+    }
 }
 
-/// Pick ETA based on UTC month, day, hour and minute. Define also 'now'. This function is needed
-/// to make tests independent of the system time.
-fn pick_eta_with_now(
+/// Push latitude in 1/10000 minutes (27 bits) to the end of `BitVec`.
+pub(crate) fn push_latitude(bv: &mut BitVec, latitude: Option<f64>) {
+    match latitude {
+        Some(lat) => push_i64(bv, (lat * 600000.0).round() as i64, 27),
+        None => push_i64(bv, 0x3412140, 27),
+    }
+}
+
+/// Push longitude in 1/10000 minutes (28 bits) to the end of `BitVec`.
+pub(crate) fn push_longitude(bv: &mut BitVec, longitude: Option<f64>) {
+    match longitude {
+        Some(lon) => push_i64(bv, (lon * 600000.0).round() as i64, 28),
+        None => push_i64(bv, 0x6791AC0, 28),
+    }
+}
+
+/// Pick ETA based on UTC month, day, hour and minute. The missing year and other unavailable
+/// fields are completed relative to `now`.
+pub(crate) fn pick_eta(
     bv: &BitVec,
     index: usize,
     now: DateTime<Utc>,
@@ -219,9 +321,14 @@ pub(crate) fn parse_hhmmss(hhmmss: &str, now: DateTime<Utc>) -> Result<DateTime<
     parse_valid_utc(now.year(), now.month(), now.day(), hour, minute, second, 0)
 }
 
-/// Parse time fields of formats YYMMDD and HHMMSS and convert them to `DateTime<Utc>`.
-pub(crate) fn parse_yymmdd_hhmmss(yymmdd: &str, hhmmss: &str) -> Result<DateTime<Utc>, ParseError> {
-    let century = (Utc::now().year() / 100) * 100;
+/// Parse time fields of formats YYMMDD and HHMMSS and convert them to `DateTime<Utc>`. The century
+/// is taken from `now`.
+pub(crate) fn parse_yymmdd_hhmmss(
+    yymmdd: &str,
+    hhmmss: &str,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, ParseError> {
+    let century = (now.year() / 100) * 100;
     let (day, month, year) =
         parse_date(yymmdd).map_err(|_| format!("Invalid date format: {}", yymmdd))?;
     let (hour, minute, second) =
@@ -487,6 +594,103 @@ pub(crate) fn parse_longitude_m_m(
     }
 }
 
+/// Format latitude as DDMM.MMMM and hemisphere fields separated by a comma. Empty fields are
+/// returned for `None`.
+pub(crate) fn format_latitude_ddmm_mmmm(latitude: Option<f64>) -> String {
+    match latitude {
+        Some(lat) => {
+            let (d, m) = split_degrees_minutes(lat);
+            format!("{:02}{},{}", d, m, if lat < 0.0 { "S" } else { "N" })
+        }
+        None => ",".into(),
+    }
+}
+
+/// Format longitude as DDDMM.MMMM and hemisphere fields separated by a comma. Empty fields are
+/// returned for `None`.
+pub(crate) fn format_longitude_dddmm_mmmm(longitude: Option<f64>) -> String {
+    match longitude {
+        Some(lon) => {
+            let (d, m) = split_degrees_minutes(lon);
+            format!("{:03}{},{}", d, m, if lon < 0.0 { "W" } else { "E" })
+        }
+        None => ",".into(),
+    }
+}
+
+/// Split the absolute value of the given angle into whole degrees and MM.MMMM minutes string.
+fn split_degrees_minutes(angle: f64) -> (u64, String) {
+    let total = (angle.abs() * 600000.0).round() as u64;
+    let minutes = total % 600000;
+    (
+        total / 600000,
+        format!("{:02}.{:04}", minutes / 10000, minutes % 10000),
+    )
+}
+
+/// Format time of the given timestamp as HHMMSS.SS or an empty field for `None`.
+pub(crate) fn format_hhmmss_ss(timestamp: Option<DateTime<Utc>>) -> String {
+    match timestamp {
+        Some(ts) => format!(
+            "{:02}{:02}{:02}.{:02}",
+            ts.hour(),
+            ts.minute(),
+            ts.second(),
+            ts.nanosecond().min(999_999_999) / 10_000_000
+        ),
+        None => "".into(),
+    }
+}
+
+/// Format date of the given timestamp as DDMMYY or an empty field for `None`.
+pub(crate) fn format_ddmmyy(timestamp: Option<DateTime<Utc>>) -> String {
+    match timestamp {
+        Some(ts) => format!("{:02}{:02}{:02}", ts.day(), ts.month(), ts.year() % 100),
+        None => "".into(),
+    }
+}
+
+/// Format the given number with fixed number of decimals or an empty field for `None`.
+pub(crate) fn format_f64_field(value: Option<f64>, decimals: usize) -> String {
+    match value {
+        Some(v) => format!("{:.*}", decimals, v),
+        None => "".into(),
+    }
+}
+
+/// Format the given number padded with zeros to the given width or an empty field for `None`.
+pub(crate) fn format_padded_field<T: std::fmt::Display>(value: Option<T>, width: usize) -> String {
+    match value {
+        Some(v) => format!("{:0>1$}", v, width),
+        None => "".into(),
+    }
+}
+
+/// Serde support for optional time zone offsets which are stored as seconds east of UTC.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_fixed_offset {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        offset.map(|o| o.local_minus_utc()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        match Option::<i32>::deserialize(deserializer)? {
+            Some(secs) => FixedOffset::east_opt(secs)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("Invalid offset: {}", secs))),
+            None => Ok(None),
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -495,7 +699,7 @@ mod test {
 
     #[test]
     fn test_parse_payload() {
-        match parse_payload(&"w7b0P1".to_string()) {
+        match parse_payload(&"w7b0P1".to_string(), 0) {
             Ok(bv) => {
                 assert_eq!(
                     bv,
@@ -513,6 +717,25 @@ mod test {
                 assert_eq!(e, "OK");
             }
         }
+
+        // Fill bits
+        match parse_payload(&"w7".to_string(), 2) {
+            Ok(bv) => {
+                assert_eq!(
+                    bv,
+                    bits![
+                        1, 1, 1, 1, 1, 1, //
+                        0, 0, 0, 1, //
+                    ]
+                );
+            }
+            Err(e) => {
+                assert_eq!(e, "OK");
+            }
+        }
+        assert!(parse_payload(&"w7".to_string(), 6).is_err());
+        assert!(parse_payload(&"w7 ".to_string(), 0).is_err());
+        assert!(parse_payload(&"".to_string(), 1).is_err());
     }
 
     #[test]
@@ -551,6 +774,48 @@ mod test {
         assert_eq!(pick_string(&bv, 0, bv.len() / 6), "?AG_4:!");
     }
 
+    #[test]
+    fn test_armor_payload() {
+        let bv = parse_payload("w7`0T", 2).unwrap();
+        assert_eq!(armor_payload(&bv), ("w7`0T".to_string(), 2));
+        assert_eq!(armor_payload(&BitVec::new()), ("".to_string(), 0));
+    }
+
+    #[test]
+    fn test_append_checksum() {
+        assert_eq!(
+            append_checksum("!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0"),
+            "!AIVDM,1,1,,A,38Id705000rRVJhE7cl9n;160000,0*40"
+        );
+    }
+
+    #[test]
+    fn test_push_fields() {
+        let mut bv = BitVec::new();
+        push_u64(&mut bv, 45, 6);
+        push_i64(&mut bv, -32, 6);
+        push_string(&mut bv, "?ag_4:!", 8);
+        assert_eq!(pick_u64(&bv, 0, 6), 45);
+        assert_eq!(pick_i64(&bv, 6, 6), -32);
+        assert_eq!(pick_string(&bv, 12, 8), "?AG_4:!");
+        assert_eq!(bv.len(), 60);
+
+        let mut bv = BitVec::new();
+        push_eta(&mut bv, Some(Utc.ymd(2021, 10, 11).and_hms(22, 57, 30)));
+        push_eta(&mut bv, None);
+        push_latitude(&mut bv, Some(-45.5));
+        push_longitude(&mut bv, None);
+        assert_eq!(
+            pick_eta(&bv, 0, Utc::now())
+                .unwrap()
+                .map(|eta| (eta.month(), eta.day(), eta.hour())),
+            Some((10, 11, 22))
+        );
+        assert_eq!(pick_eta(&bv, 20, Utc::now()).unwrap(), None);
+        assert_eq!(pick_i64(&bv, 40, 27), -27300000);
+        assert_eq!(pick_i64(&bv, 67, 28), 0x6791AC0);
+    }
+
     #[test]
     fn test_pick_eta() {
         // Valid case
@@ -560,7 +825,7 @@ mod test {
             1, 0, 1, 1, 0, // 22
             1, 1, 1, 0, 0, 1, // 57
         ];
-        let eta = pick_eta(&bv, 0).ok().unwrap();
+        let eta = pick_eta(&bv, 0, Utc::now()).ok().unwrap();
         assert_eq!(
             eta,
             Some(Utc.ymd(eta.unwrap().year(), 10, 11).and_hms(22, 57, 30))
@@ -573,7 +838,7 @@ mod test {
             1, 0, 1, 1, 0, // 22
             1, 1, 1, 0, 0, 1, // 57
         ];
-        assert_eq!(pick_eta(&bv, 0).is_ok(), false);
+        assert_eq!(pick_eta(&bv, 0, Utc::now()).is_ok(), false);
 
         // Invalid day
         let bv = bitvec![
@@ -582,7 +847,7 @@ mod test {
             1, 0, 1, 1, 0, // 22
             1, 1, 1, 0, 0, 1, // 57
         ];
-        assert_eq!(pick_eta(&bv, 0).is_ok(), false);
+        assert_eq!(pick_eta(&bv, 0, Utc::now()).is_ok(), false);
 
         // Invalid hour
         let bv = bitvec![
@@ -591,7 +856,7 @@ mod test {
             1, 1, 0, 0, 1, // 25
             1, 1, 1, 0, 0, 1, // 57
         ];
-        assert_eq!(pick_eta(&bv, 0).is_ok(), false);
+        assert_eq!(pick_eta(&bv, 0, Utc::now()).is_ok(), false);
 
         // Invalid minute
         let bv = bitvec![
@@ -600,7 +865,7 @@ mod test {
             1, 0, 1, 1, 0, // 22
             1, 1, 1, 1, 0, 1, // 61
         ];
-        assert_eq!(pick_eta(&bv, 0).is_ok(), false);
+        assert_eq!(pick_eta(&bv, 0, Utc::now()).is_ok(), false);
     }
 
     #[test]
@@ -624,36 +889,36 @@ mod test {
         // Leap day case
         let then = Utc.ymd(2020, 12, 31).and_hms(0, 0, 0);
         assert_eq!(
-            pick_eta_with_now(&feb29, 0, then).ok().unwrap(),
+            pick_eta(&feb29, 0, then).ok().unwrap(),
             Some(Utc.ymd(2020, 2, 29).and_hms(0, 0, 30))
         );
 
         // Non leap day case
         let then = Utc.ymd(2020, 12, 31).and_hms(0, 0, 0);
         assert_eq!(
-            pick_eta_with_now(&feb28, 0, then).ok().unwrap(),
+            pick_eta(&feb28, 0, then).ok().unwrap(),
             Some(Utc.ymd(2021, 2, 28).and_hms(0, 0, 30))
         );
 
         // Non leap year invalid case
         let then = Utc.ymd(2021, 12, 31).and_hms(0, 0, 0);
-        assert_eq!(pick_eta_with_now(&feb29, 0, then).is_ok(), false);
+        assert_eq!(pick_eta(&feb29, 0, then).is_ok(), false);
 
         // Non leap year valid case
         let then = Utc.ymd(2021, 12, 31).and_hms(0, 0, 0);
-        assert_eq!(pick_eta_with_now(&feb28, 0, then).is_ok(), true);
+        assert_eq!(pick_eta(&feb28, 0, then).is_ok(), true);
 
         // One day late
         let then = Utc.ymd(2021, 3, 1).and_hms(0, 0, 0);
         assert_eq!(
-            pick_eta_with_now(&feb28, 0, then).ok().unwrap(),
+            pick_eta(&feb28, 0, then).ok().unwrap(),
             Some(Utc.ymd(2021, 2, 28).and_hms(0, 0, 30))
         );
 
         // Six months late
         let then = Utc.ymd(2021, 8, 31).and_hms(0, 0, 0);
         assert_eq!(
-            pick_eta_with_now(&feb28, 0, then).ok().unwrap(),
+            pick_eta(&feb28, 0, then).ok().unwrap(),
             Some(Utc.ymd(2022, 2, 28).and_hms(0, 0, 30))
         );
     }
@@ -688,6 +953,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_format_fields() {
+        assert_eq!(format_latitude_ddmm_mmmm(Some(48.1173)), "4807.0380,N");
+        assert_eq!(format_latitude_ddmm_mmmm(Some(-5.999999999)), "0600.0000,S");
+        assert_eq!(format_latitude_ddmm_mmmm(None), ",");
+        assert_eq!(format_longitude_dddmm_mmmm(Some(11.516667)), "01131.0000,E");
+        assert_eq!(format_longitude_dddmm_mmmm(Some(-123.5)), "12330.0000,W");
+        assert_eq!(
+            format_hhmmss_ss(Some(Utc.ymd(2021, 5, 31).and_hms_milli(7, 29, 14, 250))),
+            "072914.25"
+        );
+        assert_eq!(format_hhmmss_ss(None), "");
+        assert_eq!(
+            format_ddmmyy(Some(Utc.ymd(2021, 5, 3).and_hms(7, 29, 14))),
+            "030521"
+        );
+        assert_eq!(format_f64_field(Some(0.94), 1), "0.9");
+        assert_eq!(format_f64_field(None, 1), "");
+        assert_eq!(format_padded_field(Some(7), 3), "007");
+        assert_eq!(format_padded_field::<u8>(None, 3), "");
+    }
+
     #[test]
     fn test_parse_latitude_m_m() {
         assert::close(